@@ -1,4 +1,6 @@
 use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct MinMax(i64, i64);
@@ -21,18 +23,314 @@ impl fmt::Display for Point2D {
 	}
 }
 
-#[derive(Debug)]
-struct Complex {
-	real: f64,
-	imag: f64,
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Complex<T> {
+	real: T,
+	imag: T,
+}
+
+// `x < 0` misses -0.0, a float looks at its sign bit.
+// The magnitude of an integer is unsigned: `-i32::MIN` overflows, `i32::MIN.unsigned_abs()` doesn't.
+trait Signed {
+	type Magnitude: fmt::Display;
+	
+	fn is_negative(&self) -> bool;
+	fn magnitude(self) -> Self::Magnitude;
+}
+
+macro_rules! impl_signed {
+	(int: $($t: ty => $u: ty),*; float: $($f: ty),*) => {
+		$(impl Signed for $t {
+			type Magnitude = $u;
+			
+			fn is_negative(&self) -> bool { *self < 0 }
+			fn magnitude(self) -> $u { self.unsigned_abs() }
+		})*
+		$(impl Signed for $f {
+			type Magnitude = $f;
+			
+			fn is_negative(&self) -> bool { self.is_sign_negative() }
+			fn magnitude(self) -> $f { self.abs() }
+		})*
+	};
+}
+
+impl_signed!(
+	int: i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize;
+	float: f32, f64
+);
+
+// `3.3 - 7.2i` instead of `3.3 + -7.2i`, `{:.2}` is applied to both parts
+impl<T> fmt::Display for Complex<T>
+	where T: fmt::Display + Copy + Signed {
+	
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let sign = if self.imag.is_negative() { '-' } else { '+' };
+		let imag = self.imag.magnitude();
+		
+		match f.precision() {
+			Some(p) => write!(f, "{:.*} {} {:.*}i", p, self.real, sign, p, imag),
+			None => write!(f, "{} {} {}i", self.real, sign, imag),
+		}
+	}
+}
+
+// The arithmetic only needs the operators of T, so Complex<i32> works as well
+impl<T: Add<Output = T>> Add for Complex<T> {
+	type Output = Complex<T>;
+	
+	fn add(self, rhs: Self) -> Self::Output {
+		Complex { real: self.real + rhs.real, imag: self.imag + rhs.imag }
+	}
+}
+
+impl<T: Sub<Output = T>> Sub for Complex<T> {
+	type Output = Complex<T>;
+	
+	fn sub(self, rhs: Self) -> Self::Output {
+		Complex { real: self.real - rhs.real, imag: self.imag - rhs.imag }
+	}
+}
+
+// (a + bi)(c + di) = (ac - bd) + (ad + bc)i
+impl<T> Mul for Complex<T>
+	where T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> {
+	
+	type Output = Complex<T>;
+	
+	fn mul(self, rhs: Self) -> Self::Output {
+		Complex {
+			real: self.real * rhs.real - self.imag * rhs.imag,
+			imag: self.real * rhs.imag + self.imag * rhs.real,
+		}
+	}
+}
+
+// (a + bi) / (c + di) = (a + bi)(c - di) / (c² + d²)
+impl<T> Div for Complex<T>
+	where T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> {
+	
+	type Output = Complex<T>;
+	
+	fn div(self, rhs: Self) -> Self::Output {
+		let denominator = rhs.real * rhs.real + rhs.imag * rhs.imag;
+		Complex {
+			real: (self.real * rhs.real + self.imag * rhs.imag) / denominator,
+			imag: (self.imag * rhs.real - self.real * rhs.imag) / denominator,
+		}
+	}
+}
+
+impl<T: Neg<Output = T>> Neg for Complex<T> {
+	type Output = Complex<T>;
+	
+	fn neg(self) -> Self::Output {
+		Complex { real: -self.real, imag: -self.imag }
+	}
+}
+
+impl<T: Neg<Output = T>> Complex<T> {
+	fn conjugate(self) -> Self {
+		Complex { real: self.real, imag: -self.imag }
+	}
+}
+
+// The functions of f32/f64 needed by modulus, argument, exp, ln...
+// they are inherent methods, a trait is needed to use them from generic code
+trait Float: Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self> {
+	fn hypot(self, other: Self) -> Self;
+	fn atan2(self, other: Self) -> Self;
+	fn exp(self) -> Self;
+	fn ln(self) -> Self;
+	fn powf(self, n: Self) -> Self;
+	fn sin(self) -> Self;
+	fn cos(self) -> Self;
+}
+
+macro_rules! impl_float {
+	($($t: ty),*) => {
+		$(
+			impl Float for $t {
+				fn hypot(self, other: Self) -> Self { <$t>::hypot(self, other) }
+				fn atan2(self, other: Self) -> Self { <$t>::atan2(self, other) }
+				fn exp(self) -> Self { <$t>::exp(self) }
+				fn ln(self) -> Self { <$t>::ln(self) }
+				fn powf(self, n: Self) -> Self { <$t>::powf(self, n) }
+				fn sin(self) -> Self { <$t>::sin(self) }
+				fn cos(self) -> Self { <$t>::cos(self) }
+			}
+		)*
+	};
 }
 
-impl fmt::Display for Complex {
+impl_float!(f32, f64);
+
+impl<T: Float> Complex<T> {
+	// r·(cos θ + i·sin θ)
+	fn from_polar(modulus: T, argument: T) -> Self {
+		Complex { real: modulus * argument.cos(), imag: modulus * argument.sin() }
+	}
+	
+	// |z|, hypot avoids the overflow of sqrt(a² + b²)
+	fn modulus(&self) -> T {
+		self.real.hypot(self.imag)
+	}
+	
+	// the angle in (-π, π]
+	fn argument(&self) -> T {
+		self.imag.atan2(self.real)
+	}
+	
+	fn to_polar(self) -> (T, T) {
+		(self.modulus(), self.argument())
+	}
+	
+	// e^(a + bi) = e^a·(cos b + i·sin b)
+	fn exp(self) -> Self {
+		Complex::from_polar(self.real.exp(), self.imag)
+	}
+	
+	// principal value: ln|z| + i·arg(z)
+	fn ln(self) -> Self {
+		Complex { real: self.modulus().ln(), imag: self.argument() }
+	}
+	
+	// z^n = r^n·(cos nθ + i·sin nθ), principal value
+	fn powf(self, n: T) -> Self {
+		let (r, theta) = self.to_polar();
+		Complex::from_polar(r.powf(n), theta * n)
+	}
+}
+
+#[derive(Debug, PartialEq)]
+enum ParseComplexError<E> {
+	Empty,
+	// the real or imaginary part isn't a valid T
+	Part(E),
+}
+
+impl<E: fmt::Display> fmt::Display for ParseComplexError<E> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{} + {}i", self.real, self.imag)
+		match self {
+			ParseComplexError::Empty => write!(f, "cannot parse complex number from empty string"),
+			ParseComplexError::Part(e) => write!(f, "invalid complex number: {}", e),
+		}
 	}
 }
 
+// Accepts `a+bi`, `a - bi`, `bi`, `a`, `i`, `-i`, `1e-3+2E+4i`...
+// Whitespace is only allowed at the edges and around the sign between both parts,
+// `1 2` and `3 4 i` are errors of T::from_str.
+// The parts are handed to T::from_str, so T doesn't need to know what 0 or 1 is.
+impl<T: FromStr> FromStr for Complex<T> {
+	type Err = ParseComplexError<T::Err>;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if s.is_empty() {
+			return Err(ParseComplexError::Empty);
+		}
+		
+		let (real, imag) = match s.strip_suffix('i') {
+			None => (s, String::from("0")),
+			Some(rest) => {
+				// the sign between both parts: not the leading one, not the one of an exponent
+				let split = rest.char_indices().rev().find(|&(i, c)| {
+					(c == '+' || c == '-') && i > 0 && !rest[..i].ends_with(['e', 'E'])
+				});
+				match split {
+					Some((i, sign)) => (rest[..i].trim_end(), format!("{}{}", sign, rest[i + 1..].trim_start())),
+					None => ("0", rest.to_string()),
+				}
+			}
+		};
+		
+		// `i` alone means `1i`
+		let imag = match imag.as_str() {
+			"" | "+" => "1",
+			"-" => "-1",
+			imag => imag,
+		};
+		
+		Ok(Complex {
+			real: real.parse().map_err(ParseComplexError::Part)?,
+			imag: imag.parse().map_err(ParseComplexError::Part)?,
+		})
+	}
+}
+
+fn approx_eq(a: Complex<f64>, b: Complex<f64>) -> bool {
+	(a - b).modulus() < 1e-9
+}
+
+fn complex_numbers() {
+	println!("Complex numbers:");
+	let a = Complex { real: 3.0, imag: 4.0 };
+	let b = Complex { real: 1.0, imag: -2.0 };
+	
+	println!("a = {}, b = {}", a, b);
+	println!("a + b = {}", a + b);
+	println!("a - b = {}", a - b);
+	println!("a * b = {}", a * b);
+	println!("a / b = {:.3}", a / b);
+	
+	assert_eq!(format!("{}", b), "1 - 2i");
+	assert_eq!(format!("{:.2}", b), "1.00 - 2.00i");
+	// the sign of -0.0 is taken out too
+	assert_eq!(Complex { real: 1.0, imag: -0.0 }.to_string(), "1 - 0i");
+	assert_eq!(format!("{:.1}", Complex { real: -0.0, imag: -0.0 }), "-0.0 - 0.0i");
+	assert_eq!(a + b, Complex { real: 4.0, imag: 2.0 });
+	assert_eq!(a - b, Complex { real: 2.0, imag: 6.0 });
+	assert_eq!(a * b, Complex { real: 11.0, imag: -2.0 });
+	assert!(approx_eq(a / b * b, a));
+	assert_eq!(-a, Complex { real: -3.0, imag: -4.0 });
+	assert_eq!(a.conjugate(), Complex { real: 3.0, imag: -4.0 });
+	
+	// integer complex numbers only get the operators
+	let gaussian = Complex { real: 2, imag: 3 } * Complex { real: 2, imag: -3 };
+	assert_eq!(gaussian, Complex { real: 13, imag: 0 });
+	assert_eq!(gaussian.to_string(), "13 + 0i");
+	// the magnitude of i32::MIN doesn't fit in an i32
+	assert_eq!(Complex { real: 0, imag: i32::MIN }.to_string(), "0 - 2147483648i");
+	assert_eq!(Complex { real: 0i8, imag: i8::MIN }.to_string(), "0 - 128i");
+	
+	assert_eq!(a.modulus(), 5.0);
+	let (r, theta) = b.to_polar();
+	assert!(approx_eq(Complex::from_polar(r, theta), b));
+	let i = Complex { real: 0.0, imag: 1.0 };
+	assert!((i.argument() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+	
+	// Euler's identity: e^(iπ) + 1 = 0
+	let pi_i = Complex { real: 0.0, imag: std::f64::consts::PI };
+	let euler = pi_i.exp() + Complex { real: 1.0, imag: 0.0 };
+	println!("e^(iπ) + 1 = {:.3}", euler);
+	assert!(approx_eq(euler, Complex::default()));
+	assert!(approx_eq(a.ln().exp(), a));
+	// i² = -1, √(-4) = 2i
+	assert!(approx_eq(i.powf(2.0), Complex { real: -1.0, imag: 0.0 }));
+	assert!(approx_eq(Complex { real: -4.0, imag: 0.0 }.powf(0.5), Complex { real: 0.0, imag: 2.0 }));
+	
+	let f32_complex = Complex { real: 1.0f32, imag: 1.0 };
+	assert!((f32_complex.modulus() - 2f32.sqrt()).abs() < 1e-6);
+	
+	assert_eq!("3.3+7.2i".parse(), Ok(Complex { real: 3.3, imag: 7.2 }));
+	assert_eq!("3.3 - 7.2i".parse(), Ok(Complex { real: 3.3, imag: -7.2 }));
+	assert_eq!("-7.2i".parse(), Ok(Complex { real: 0.0, imag: -7.2 }));
+	assert_eq!("-i".parse(), Ok(Complex { real: 0.0, imag: -1.0 }));
+	assert_eq!("5".parse(), Ok(Complex { real: 5, imag: 0 }));
+	assert_eq!("1e-3+2E+1i".parse(), Ok(Complex { real: 0.001, imag: 20.0 }));
+	assert_eq!("".parse::<Complex<f64>>(), Err(ParseComplexError::Empty));
+	assert!("3 + xi".parse::<Complex<f64>>().is_err());
+	assert_eq!(" 3 +  4i ".parse(), Ok(Complex { real: 3, imag: 4 }));
+	// no whitespace inside a number
+	assert!("1 2".parse::<Complex<f64>>().is_err());
+	assert!("3 4 i".parse::<Complex<f64>>().is_err());
+	assert!("3 + 4 i".parse::<Complex<f64>>().is_err());
+	// Display and FromStr round trip
+	let parsed: Complex<f64> = b.to_string().parse().unwrap();
+	assert_eq!(parsed, b);
+}
+
 struct List(Vec<i32>);
 
 impl fmt::Display for List {
@@ -60,16 +358,401 @@ struct City {
 	lon: f32,
 }
 
+impl City {
+	fn location(&self) -> GeoPoint {
+		GeoPoint { lat: self.lat as f64, lon: self.lon as f64 }
+	}
+}
+
 impl fmt::Display for City {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
-		let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+		write!(f, "{}: {}", self.name, self.location())
+	}
+}
+
+// The hemisphere letter of a latitude (N/S) or a longitude (E/W)
+fn hemisphere(value: f64, positive: char, negative: char) -> char {
+	if value >= 0.0 { positive } else { negative }
+}
+
+// Mean earth radius in meters, used by the spherical formulas
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+// latitude and longitude in degrees, south and west are negative
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GeoPoint {
+	lat: f64,
+	lon: f64,
+}
+
+// longitude back into [-180, 180)
+fn normalize_lon(lon: f64) -> f64 {
+	(lon + 540.0).rem_euclid(360.0) - 180.0
+}
+
+impl GeoPoint {
+	// great-circle distance in meters on a sphere
+	fn haversine_distance(&self, other: &GeoPoint) -> f64 {
+		let (phi1, phi2) = (self.lat.to_radians(), other.lat.to_radians());
+		let d_phi = phi2 - phi1;
+		let d_lambda = (other.lon - self.lon).to_radians();
 		
-		write!(f, "{}: {:.3}°{} {:.3}°{}", 
-			self.name, self.lat.abs(), lat_c, self.lon.abs(), lon_c)
+		let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+		// rounding can take `a` above 1 for antipodal points, and the square root of 1 - a to NaN
+		let a = a.clamp(0.0, 1.0);
+		2.0 * EARTH_RADIUS * a.sqrt().atan2((1.0 - a).sqrt())
+	}
+	
+	// distance in meters on the WGS-84 ellipsoid (Vincenty's inverse formula)
+	// None when the iteration doesn't converge, which happens for nearly antipodal points
+	fn vincenty_distance(&self, other: &GeoPoint) -> Option<f64> {
+		const A: f64 = 6_378_137.0;
+		const F: f64 = 1.0 / 298.257_223_563;
+		const B: f64 = (1.0 - F) * A;
+		
+		let l = (other.lon - self.lon).to_radians();
+		let u1 = ((1.0 - F) * self.lat.to_radians().tan()).atan();
+		let u2 = ((1.0 - F) * other.lat.to_radians().tan()).atan();
+		let (sin_u1, cos_u1) = u1.sin_cos();
+		let (sin_u2, cos_u2) = u2.sin_cos();
+		
+		let mut lambda = l;
+		for _ in 0..200 {
+			let (sin_lambda, cos_lambda) = lambda.sin_cos();
+			let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+				+ (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2)).sqrt();
+			if sin_sigma == 0.0 {
+				// coincident points
+				return Some(0.0);
+			}
+			let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+			let sigma = sin_sigma.atan2(cos_sigma);
+			let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+			let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+			// both points on the equator
+			let cos_2sigma_m = if cos2_alpha != 0.0 {
+				cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+			} else {
+				0.0
+			};
+			let c = F / 16.0 * cos2_alpha * (4.0 + F * (4.0 - 3.0 * cos2_alpha));
+			
+			let previous = lambda;
+			lambda = l + (1.0 - c) * F * sin_alpha
+				* (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+			
+			if (lambda - previous).abs() < 1e-12 {
+				let u_sq = cos2_alpha * (A * A - B * B) / (B * B);
+				let big_a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+				let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+				let delta_sigma = big_b * sin_sigma * (cos_2sigma_m + big_b / 4.0
+					* (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+						- big_b / 6.0 * cos_2sigma_m * (-3.0 + 4.0 * sin_sigma.powi(2)) * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+				
+				return Some(B * big_a * (sigma - delta_sigma));
+			}
+		}
+		
+		None
+	}
+	
+	// the bearing to start with when going to `other`, in degrees from north [0, 360)
+	fn initial_bearing(&self, other: &GeoPoint) -> f64 {
+		let (phi1, phi2) = (self.lat.to_radians(), other.lat.to_radians());
+		let d_lambda = (other.lon - self.lon).to_radians();
+		
+		let y = d_lambda.sin() * phi2.cos();
+		let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * d_lambda.cos();
+		y.atan2(x).to_degrees().rem_euclid(360.0)
+	}
+	
+	// the half-way point along the great circle
+	fn midpoint(&self, other: &GeoPoint) -> GeoPoint {
+		let (phi1, phi2) = (self.lat.to_radians(), other.lat.to_radians());
+		let d_lambda = (other.lon - self.lon).to_radians();
+		
+		let bx = phi2.cos() * d_lambda.cos();
+		let by = phi2.cos() * d_lambda.sin();
+		let phi = (phi1.sin() + phi2.sin()).atan2(((phi1.cos() + bx).powi(2) + by * by).sqrt());
+		let lambda = self.lon.to_radians() + by.atan2(phi1.cos() + bx);
+		
+		GeoPoint { lat: phi.to_degrees(), lon: normalize_lon(lambda.to_degrees()) }
+	}
+	
+	// the smallest latitude/longitude box holding every point within `radius` meters
+	fn bounding_box(&self, radius: f64) -> BoundingBox {
+		let delta = radius / EARTH_RADIUS;
+		let phi = self.lat.to_radians();
+		let (south, north) = (phi - delta, phi + delta);
+		
+		// a pole is inside the circle: every longitude is
+		if north >= std::f64::consts::FRAC_PI_2 || south <= -std::f64::consts::FRAC_PI_2 {
+			return BoundingBox {
+				south: south.to_degrees().max(-90.0),
+				north: north.to_degrees().min(90.0),
+				west: -180.0,
+				east: 180.0,
+			};
+		}
+		
+		let d_lambda = (delta.sin() / phi.cos()).asin().to_degrees();
+		BoundingBox {
+			south: south.to_degrees(),
+			north: north.to_degrees(),
+			west: normalize_lon(self.lon - d_lambda),
+			east: normalize_lon(self.lon + d_lambda),
+		}
+	}
+}
+
+// `west > east` when the box crosses the antimeridian
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BoundingBox {
+	south: f64,
+	west: f64,
+	north: f64,
+	east: f64,
+}
+
+impl BoundingBox {
+	fn contains(&self, point: &GeoPoint) -> bool {
+		let lon_inside = if self.west <= self.east {
+			self.west <= point.lon && point.lon <= self.east
+		} else {
+			point.lon >= self.west || point.lon <= self.east
+		};
+		
+		self.south <= point.lat && point.lat <= self.north && lon_inside
 	}
 }
 
+// `{}` prints decimal degrees like `53.348°N 6.260°W` (`{:.1}` changes the digits),
+// `{:#}` prints degrees, minutes and seconds like `53°20'52"N 6°15'35"W` (`{:#.2}` for the seconds)
+impl fmt::Display for GeoPoint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let lat_c = hemisphere(self.lat, 'N', 'S');
+		let lon_c = hemisphere(self.lon, 'E', 'W');
+		
+		if f.alternate() {
+			let precision = f.precision().unwrap_or(0);
+			write_dms(f, self.lat.abs(), precision)?;
+			write!(f, "{} ", lat_c)?;
+			write_dms(f, self.lon.abs(), precision)?;
+			write!(f, "{}", lon_c)
+		} else {
+			let precision = f.precision().unwrap_or(3);
+			write!(f, "{:.*}°{} {:.*}°{}", 
+				precision, self.lat.abs(), lat_c, precision, self.lon.abs(), lon_c)
+		}
+	}
+}
+
+fn write_dms(f: &mut fmt::Formatter, degrees: f64, precision: usize) -> fmt::Result {
+	// round once on the total, so 59.9999" becomes 1' and not 60"
+	let scale = 10f64.powi(precision as i32);
+	let total = (degrees * 3600.0 * scale).round() / scale;
+	let d = (total / 3600.0).floor();
+	let m = ((total - d * 3600.0) / 60.0).floor();
+	let s = total - d * 3600.0 - m * 60.0;
+	
+	write!(f, "{}°{}'{:.*}\"", d, m, precision, s.max(0.0))
+}
+
+#[derive(Debug, PartialEq)]
+enum ParseGeoError {
+	Empty,
+	InvalidNumber,
+	// minutes or seconds not below 60, latitude over 90 or longitude over 180
+	OutOfRange,
+	// an E/W longitude given where the latitude is expected and so on
+	WrongHemisphere,
+	// not exactly a latitude and a longitude
+	WrongCount,
+}
+
+impl fmt::Display for ParseGeoError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let msg = match self {
+			ParseGeoError::Empty => "cannot parse coordinate from empty string",
+			ParseGeoError::InvalidNumber => "invalid number in coordinate",
+			ParseGeoError::OutOfRange => "coordinate out of range",
+			ParseGeoError::WrongHemisphere => "hemisphere doesn't match the coordinate",
+			ParseGeoError::WrongCount => "expected a latitude and a longitude",
+		};
+		write!(f, "{}", msg)
+	}
+}
+
+// One coordinate: `53°20'52"N`, `53°20.5'N`, `-6.26`, `6°15'35.2"W`...
+// `positive`/`negative` are the allowed hemisphere letters, `max` the largest degree value.
+fn parse_dms(s: &str, positive: char, negative: char, max: f64) -> Result<f64, ParseGeoError> {
+	let s = s.trim();
+	if s.is_empty() {
+		return Err(ParseGeoError::Empty);
+	}
+	
+	let (body, sign) = match s.chars().last().map(|c| c.to_ascii_uppercase()) {
+		Some(c) if c == positive => (&s[..s.len() - 1], 1.0),
+		Some(c) if c == negative => (&s[..s.len() - 1], -1.0),
+		Some('N') | Some('S') | Some('E') | Some('W') => return Err(ParseGeoError::WrongHemisphere),
+		_ => match s.strip_prefix('-') {
+			Some(rest) => (rest, -1.0),
+			None => (s, 1.0),
+		},
+	};
+	
+	// "NaN" and "inf" are f64s too, but no angle
+	let number = |text: &str| {
+		text.parse::<f64>().ok().filter(|v| v.is_finite()).ok_or(ParseGeoError::InvalidNumber)
+	};
+	
+	// degrees, minutes and seconds, each ended by its marker
+	let markers: [&[char]; 3] = [&['°'], &['\'', '′'], &['"', '″']];
+	let mut parts = [0.0; 3];
+	let mut rest = body.trim();
+	let mut found = false;
+	for (part, marker) in parts.iter_mut().zip(markers.iter()) {
+		if let Some(pos) = rest.find(*marker) {
+			*part = number(rest[..pos].trim())?;
+			let marker_len = rest[pos..].chars().next().map_or(1, char::len_utf8);
+			rest = rest[pos + marker_len..].trim_start();
+			found = true;
+		}
+	}
+	
+	if !rest.is_empty() {
+		if found {
+			return Err(ParseGeoError::InvalidNumber);
+		}
+		// plain decimal degrees
+		parts[0] = number(rest)?;
+	}
+	
+	let [d, m, sec] = parts;
+	if d < 0.0 || !(0.0..60.0).contains(&m) || !(0.0..60.0).contains(&sec) {
+		return Err(ParseGeoError::OutOfRange);
+	}
+	let degrees = d + m / 60.0 + sec / 3600.0;
+	if degrees > max {
+		return Err(ParseGeoError::OutOfRange);
+	}
+	
+	Ok(sign * degrees)
+}
+
+// `<latitude> <longitude>`, separated by a comma or a white space
+impl FromStr for GeoPoint {
+	type Err = ParseGeoError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parts: Vec<&str> = if s.contains(',') {
+			s.split(',').collect()
+		} else {
+			s.split_whitespace().collect()
+		};
+		
+		match parts.as_slice() {
+			[lat, lon] => Ok(GeoPoint {
+				lat: parse_dms(lat, 'N', 'S', 90.0)?,
+				lon: parse_dms(lon, 'E', 'W', 180.0)?,
+			}),
+			[] => Err(ParseGeoError::Empty),
+			_ => Err(ParseGeoError::WrongCount),
+		}
+	}
+}
+
+fn geo_points() {
+	println!("Geographic points:");
+	let dublin: GeoPoint = "53°20'52\"N 6°15'35\"W".parse().unwrap();
+	let oslo = GeoPoint { lat: 59.95, lon: 10.75 };
+	
+	println!("Dublin: {} / {:#}", dublin, dublin);
+	println!("Dublin -> Oslo: {:.1} km (haversine), {:.1} km (Vincenty), bearing {:.1}°, midpoint {}",
+		dublin.haversine_distance(&oslo) / 1000.0,
+		dublin.vincenty_distance(&oslo).unwrap() / 1000.0,
+		dublin.initial_bearing(&oslo),
+		dublin.midpoint(&oslo));
+	
+	assert_eq!(format!("{:#}", dublin), "53°20'52\"N 6°15'35\"W");
+	assert_eq!(format!("{:.1}", dublin), "53.3°N 6.3°W");
+	assert_eq!(format!("{:#.1}", GeoPoint { lat: -0.5, lon: 12.345 }), "0°30'0.0\"S 12°20'42.0\"E");
+	// rounding carries into the minutes instead of printing 60"
+	assert_eq!(format!("{:#}", GeoPoint { lat: 10.999_999, lon: 0.0 }), "11°0'0\"N 0°0'0\"E");
+	
+	// the DMS output parses back
+	assert_eq!(format!("{:#}", dublin).parse::<GeoPoint>(), Ok(dublin));
+	assert_eq!("53.5, -6.25".parse(), Ok(GeoPoint { lat: 53.5, lon: -6.25 }));
+	assert_eq!("53°30.5'N, 6°W".parse(), Ok(GeoPoint { lat: 53.0 + 30.5 / 60.0, lon: -6.0 }));
+	assert_eq!("6°W 53°N".parse::<GeoPoint>(), Err(ParseGeoError::WrongHemisphere));
+	assert_eq!("91°N 0°E".parse::<GeoPoint>(), Err(ParseGeoError::OutOfRange));
+	assert_eq!("53°61'N 0°E".parse::<GeoPoint>(), Err(ParseGeoError::OutOfRange));
+	assert_eq!("53°x'N 0°E".parse::<GeoPoint>(), Err(ParseGeoError::InvalidNumber));
+	assert_eq!("NaN NaN".parse::<GeoPoint>(), Err(ParseGeoError::InvalidNumber));
+	assert_eq!("NaN°N 0°E".parse::<GeoPoint>(), Err(ParseGeoError::InvalidNumber));
+	assert_eq!("53 -inf".parse::<GeoPoint>(), Err(ParseGeoError::InvalidNumber));
+	assert_eq!("53°N".parse::<GeoPoint>(), Err(ParseGeoError::WrongCount));
+	assert_eq!("".parse::<GeoPoint>(), Err(ParseGeoError::Empty));
+	
+	// Vincenty's own test line: Flinders Peak -> Buninyong, 54972.271 m
+	let flinders: GeoPoint = "37°57'3.72030\"S, 144°25'29.52440\"E".parse().unwrap();
+	let buninyong: GeoPoint = "37°39'10.15610\"S, 143°55'35.38390\"E".parse().unwrap();
+	let vincenty = flinders.vincenty_distance(&buninyong).unwrap();
+	assert!((vincenty - 54_972.271).abs() < 0.001);
+	// the sphere is close to the ellipsoid
+	let haversine = flinders.haversine_distance(&buninyong);
+	assert!((haversine - vincenty).abs() / vincenty < 0.005);
+	assert_eq!(dublin.vincenty_distance(&dublin), Some(0.0));
+	// antipodal points on the equator don't converge
+	assert_eq!(GeoPoint { lat: 0.0, lon: 0.0 }.vincenty_distance(&GeoPoint { lat: 0.5, lon: 179.7 }), None);
+	
+	let north_pole = GeoPoint { lat: 90.0, lon: 0.0 };
+	let equator = GeoPoint { lat: 0.0, lon: 0.0 };
+	// antipodal points are half a great circle apart, not NaN
+	let half_circle = std::f64::consts::PI * EARTH_RADIUS;
+	assert!((equator.haversine_distance(&GeoPoint { lat: 0.0, lon: 180.0 }) - half_circle).abs() < 1e-6);
+	assert!((north_pole.haversine_distance(&GeoPoint { lat: -90.0, lon: 0.0 }) - half_circle).abs() < 1e-6);
+	for lat in -90..=90 {
+		for lon in (-180..180).step_by(15) {
+			let p = GeoPoint { lat: lat as f64, lon: lon as f64 };
+			let antipode = GeoPoint { lat: -p.lat, lon: normalize_lon(p.lon + 180.0) };
+			// `1 - a` loses its digits near the antipode, the distance is still within a meter
+			assert!((p.haversine_distance(&antipode) - half_circle).abs() < 1.0);
+		}
+	}
+	assert!((equator.initial_bearing(&north_pole) - 0.0).abs() < 1e-9);
+	assert!((equator.initial_bearing(&GeoPoint { lat: 0.0, lon: 10.0 }) - 90.0).abs() < 1e-9);
+	assert!((north_pole.initial_bearing(&equator) - 180.0).abs() < 1e-9);
+	assert!((equator.midpoint(&north_pole).lat - 45.0).abs() < 1e-9);
+	let across = GeoPoint { lat: 0.0, lon: 170.0 }.midpoint(&GeoPoint { lat: 0.0, lon: -170.0 });
+	assert!((across.lon.abs() - 180.0).abs() < 1e-9);
+	
+	// bounding box queries
+	let cities = [
+		City { name: "Dublin", lat: 53.347_78, lon: -6.259722 },
+		City { name: "Belfast", lat: 54.597, lon: -5.93 },
+		City { name: "London", lat: 51.507, lon: -0.128 },
+		City { name: "Oslo", lat: 59.95, lon: 10.75 },
+	];
+	let area = dublin.bounding_box(200_000.0);
+	let near: Vec<&str> = cities.iter().filter(|c| area.contains(&c.location())).map(|c| c.name).collect();
+	println!("Within the 200 km box around Dublin: {:?}", near);
+	assert_eq!(near, ["Dublin", "Belfast"]);
+	// every city in the box is within the radius, the box is only a pre-filter
+	assert!(cities.iter().filter(|c| area.contains(&c.location()))
+		.all(|c| dublin.haversine_distance(&c.location()) < 200_000.0));
+	
+	let fiji = GeoPoint { lat: -17.7, lon: 178.0 };
+	let area = fiji.bounding_box(500_000.0);
+	assert!(area.west > area.east);
+	assert!(area.contains(&GeoPoint { lat: -17.0, lon: -179.0 }));
+	assert!(!area.contains(&GeoPoint { lat: -17.0, lon: 0.0 }));
+	
+	let polar = GeoPoint { lat: 89.0, lon: 45.0 }.bounding_box(500_000.0);
+	assert_eq!((polar.north, polar.west, polar.east), (90.0, -180.0, 180.0));
+}
+
+// `Rgb` in the `color` module of 20_color.rs, which converts it to the other color models
 #[derive(Debug)]
 struct Color {
 	red: u8,
@@ -116,6 +799,8 @@ fn main() {
 	println!("Display: {}", complex);
 	println!("Debug: {:?}", complex);
 	
+	complex_numbers();
+	
 	let list = List(vec![1, 2, 3]);
 	println!("{}", list);
 	
@@ -127,6 +812,8 @@ fn main() {
         println!("{}", *city);
     }
 	
+	geo_points();
+	
 	for color in [
         Color { red: 128, green: 255, blue: 90 },
         Color { red: 0, green: 3, blue: 254 },
@@ -92,6 +92,298 @@ fn casting() {
     }
 }
 
+// `as` truncates, saturates or wraps silently. `NumCast` makes the choice explicit:
+// `try_cast` (and `checked_cast`) report why the value doesn't fit, `saturating_cast` clamps
+// to the bounds of the target and `wrapping_cast` keeps the low bits, as `as` does for integers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CastError {
+	// above the maximum of the target
+	Overflow,
+	// below the minimum of the target
+	Underflow,
+	// a NaN has no integer value
+	NaN,
+	// a float with a fractional part cast to an integer
+	FractionalLoss,
+	// the float closest to the value isn't the value (u64::MAX as f64, 0.1f64 as f32...)
+	PrecisionLoss,
+}
+
+impl std::fmt::Display for CastError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let msg = match self {
+			CastError::Overflow => "value above the maximum of the target type",
+			CastError::Underflow => "value below the minimum of the target type",
+			CastError::NaN => "NaN can't be cast to an integer",
+			CastError::FractionalLoss => "the fractional part would be lost",
+			CastError::PrecisionLoss => "the value isn't exactly representable",
+		};
+		write!(f, "{}", msg)
+	}
+}
+
+// Every primitive value fits in one of these without loss (f32 widens exactly to f64)
+#[derive(Debug, Clone, Copy)]
+enum Repr {
+	Signed(i128),
+	Unsigned(u128),
+	Float(f64),
+}
+
+trait NumCast: Copy {
+	fn to_repr(self) -> Repr;
+	fn try_from_repr(value: Repr) -> Result<Self, CastError>;
+	fn saturating_from_repr(value: Repr) -> Self;
+	fn wrapping_from_repr(value: Repr) -> Self;
+	
+	fn try_cast<T: NumCast>(self) -> Result<T, CastError> {
+		T::try_from_repr(self.to_repr())
+	}
+	
+	fn checked_cast<T: NumCast>(self) -> Option<T> {
+		self.try_cast().ok()
+	}
+	
+	fn saturating_cast<T: NumCast>(self) -> T {
+		T::saturating_from_repr(self.to_repr())
+	}
+	
+	fn wrapping_cast<T: NumCast>(self) -> T {
+		T::wrapping_from_repr(self.to_repr())
+	}
+}
+
+// A float truncated then taken modulo 2^128, so that `as` on the result wraps like an integer cast.
+// NaN and the infinities give 0.
+fn wrap_float(f: f64) -> u128 {
+	if !f.is_finite() {
+		0
+	} else if f.abs() < 2f64.powi(127) {
+		f.trunc() as i128 as u128
+	} else {
+		// so large that it's an integer, and the remainder is exact
+		f.rem_euclid(2f64.powi(128)) as u128
+	}
+}
+
+macro_rules! impl_int_cast {
+	($repr: ident: $($t: ty),*) => {
+		$(
+			impl NumCast for $t {
+				fn to_repr(self) -> Repr {
+					Repr::$repr(self as _)
+				}
+				
+				fn try_from_repr(value: Repr) -> Result<Self, CastError> {
+					use std::convert::TryFrom;
+					match value {
+						Repr::Signed(v) => <$t>::try_from(v)
+							.map_err(|_| if v < 0 { CastError::Underflow } else { CastError::Overflow }),
+						Repr::Unsigned(v) => <$t>::try_from(v).map_err(|_| CastError::Overflow),
+						Repr::Float(f) => {
+							// MIN is 0 or -2^n, exact in f64, MAX + 1 rounds to 2^n
+							if f.is_nan() {
+								Err(CastError::NaN)
+							} else if f < <$t>::MIN as f64 {
+								Err(CastError::Underflow)
+							} else if f >= <$t>::MAX as f64 + 1.0 {
+								Err(CastError::Overflow)
+							} else if f.fract() != 0.0 {
+								Err(CastError::FractionalLoss)
+							} else {
+								Ok(f as $t)
+							}
+						}
+					}
+				}
+				
+				fn saturating_from_repr(value: Repr) -> Self {
+					use std::convert::TryFrom;
+					match value {
+						Repr::Signed(v) => <$t>::try_from(v).unwrap_or(if v < 0 { <$t>::MIN } else { <$t>::MAX }),
+						Repr::Unsigned(v) => <$t>::try_from(v).unwrap_or(<$t>::MAX),
+						// `as` already saturates floats, NaN gives 0
+						Repr::Float(f) => f as $t,
+					}
+				}
+				
+				fn wrapping_from_repr(value: Repr) -> Self {
+					match value {
+						Repr::Signed(v) => v as $t,
+						Repr::Unsigned(v) => v as $t,
+						Repr::Float(f) => wrap_float(f) as $t,
+					}
+				}
+			}
+		)*
+	};
+}
+
+impl_int_cast!(Signed: i8, i16, i32, i64, i128, isize);
+impl_int_cast!(Unsigned: u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_float_cast {
+	($($t: ty),*) => {
+		$(
+			impl NumCast for $t {
+				fn to_repr(self) -> Repr {
+					Repr::Float(self as f64)
+				}
+				
+				// NaN and the infinities stay what they are
+				fn try_from_repr(value: Repr) -> Result<Self, CastError> {
+					let (result, exact) = match value {
+						// the rounding may go up to 2^127 or 2^128, out of the range of the source
+						Repr::Signed(v) => {
+							let r = v as $t;
+							(r, (r as f64) < 2f64.powi(127) && r as i128 == v)
+						}
+						Repr::Unsigned(v) => {
+							let r = v as $t;
+							(r, (r as f64) < 2f64.powi(128) && r as u128 == v)
+						}
+						Repr::Float(f) => {
+							let r = f as $t;
+							(r, f.is_nan() || r as f64 == f)
+						}
+					};
+					
+					if exact {
+						Ok(result)
+					} else if result.is_infinite() {
+						// only a finite value can become infinite
+						Err(if result > 0.0 { CastError::Overflow } else { CastError::Underflow })
+					} else {
+						Err(CastError::PrecisionLoss)
+					}
+				}
+				
+				// the nearest float, finite values stay finite
+				fn saturating_from_repr(value: Repr) -> Self {
+					let r = Self::wrapping_from_repr(value);
+					let finite = match value {
+						Repr::Float(f) => f.is_finite(),
+						_ => true,
+					};
+					if finite && r.is_infinite() {
+						if r > 0.0 { <$t>::MAX } else { <$t>::MIN }
+					} else {
+						r
+					}
+				}
+				
+				// nothing wraps around for floats: this is `as`
+				fn wrapping_from_repr(value: Repr) -> Self {
+					match value {
+						Repr::Signed(v) => v as $t,
+						Repr::Unsigned(v) => v as $t,
+						Repr::Float(f) => f as $t,
+					}
+				}
+			}
+		)*
+	};
+}
+
+impl_float_cast!(f32, f64);
+
+// (try_cast, saturating_cast, wrapping_cast) of a value, printed as a line of the table
+macro_rules! cast_row {
+	($value: expr => $to: ty) => {{
+		let value = $value;
+		let row = (value.try_cast::<$to>(), value.saturating_cast::<$to>(), value.wrapping_cast::<$to>());
+		println!("{:>16} as {:<5} | try: {:<22} | saturating: {:<22} | wrapping: {:?}",
+			stringify!($value), stringify!($to), format!("{:?}", row.0), format!("{:?}", row.1), row.2);
+		row
+	}};
+}
+
+// every integer type to every integer type, against TryFrom and `as` of std
+macro_rules! check_int_pairs {
+	($($from: ty),*) => {
+		$(check_int_pairs!(@from $from; u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);)*
+	};
+	(@from $from: ty; $($to: ty),*) => {
+		$(
+			for &v in [<$from>::MIN, <$from>::MIN / 2, 0, 1, 100, <$from>::MAX / 2, <$from>::MAX].iter() {
+				let expected = <$to>::try_from(v).ok();
+				assert_eq!(v.checked_cast::<$to>(), expected);
+				assert_eq!(v.saturating_cast::<$to>(), expected.unwrap_or(if v > 0 { <$to>::MAX } else { <$to>::MIN }));
+				assert_eq!(v.wrapping_cast::<$to>(), v as $to);
+			}
+		)*
+	};
+}
+
+fn num_cast() {
+	println!("~~~~~~~num cast~~~~~~~");
+	use std::convert::TryFrom;
+	
+	// the values of casting()
+	assert_eq!(cast_row!(1000_i32 => u16), (Ok(1000), 1000, 1000));
+	assert_eq!(cast_row!(1000_i32 => u8), (Err(CastError::Overflow), 255, 232));
+	assert_eq!(cast_row!(-1_i8 => u8), (Err(CastError::Underflow), 0, 255));
+	assert_eq!(cast_row!(128_i32 => i16), (Ok(128), 128, 128));
+	assert_eq!(cast_row!(128_i32 => i8), (Err(CastError::Overflow), 127, -128));
+	assert_eq!(cast_row!(232_i32 => i8), (Err(CastError::Overflow), 127, -24));
+	assert_eq!(cast_row!(65.4321_f32 => u8), (Err(CastError::FractionalLoss), 65, 65));
+	// wrapping gives what to_int_unchecked printed: 44 and 156
+	assert_eq!(cast_row!(300.0_f32 => u8), (Err(CastError::Overflow), 255, 44));
+	assert_eq!(cast_row!(-100.0_f32 => u8), (Err(CastError::Underflow), 0, 156));
+	let nan = cast_row!(f32::NAN => u8);
+	assert_eq!(nan, (Err(CastError::NaN), 0, 0));
+	
+	// the bounds of the float to integer casts
+	assert_eq!(cast_row!(255.0_f64 => u8), (Ok(255), 255, 255));
+	assert_eq!(cast_row!(-128.0_f64 => i8), (Ok(-128), -128, -128));
+	assert_eq!(cast_row!(-129.0_f64 => i8), (Err(CastError::Underflow), -128, 127));
+	assert_eq!(cast_row!(-0.5_f64 => u8), (Err(CastError::Underflow), 0, 0));
+	assert_eq!(cast_row!(-1.0_f64 => i64), (Ok(-1), -1, -1));
+	// 2^64 is u64::MAX as f64, yet it doesn't fit
+	assert_eq!(cast_row!(18446744073709551616.0_f64 => u64), (Err(CastError::Overflow), u64::MAX, 0));
+	assert_eq!(cast_row!(9223372036854775808.0_f64 => i64), (Err(CastError::Overflow), i64::MAX, i64::MIN));
+	assert_eq!(cast_row!(-9223372036854775808.0_f64 => i64), (Ok(i64::MIN), i64::MIN, i64::MIN));
+	assert_eq!(cast_row!(f64::INFINITY => i32), (Err(CastError::Overflow), i32::MAX, 0));
+	assert_eq!(cast_row!(f64::NEG_INFINITY => u128), (Err(CastError::Underflow), 0, 0));
+	assert_eq!(cast_row!(1e20_f64 => i64).2, (1e20_f64 as i128) as i64);
+	// -3.0e38 + 2^128 is exact: both are multiples of 2^104
+	assert_eq!(cast_row!(-3.0e38_f32 => i128).2, (-3.0e38_f32 as f64 + 2f64.powi(128)) as i128);
+	
+	// to the floats
+	assert_eq!(cast_row!(16777216_i32 => f32), (Ok(16777216.0), 16777216.0, 16777216.0));
+	assert_eq!(cast_row!(16777217_i32 => f32), (Err(CastError::PrecisionLoss), 16777216.0, 16777216.0));
+	assert_eq!(cast_row!(u64::MAX => f64), (Err(CastError::PrecisionLoss), u64::MAX as f64, u64::MAX as f64));
+	assert_eq!(cast_row!(i128::MAX => f64).0, Err(CastError::PrecisionLoss));
+	assert_eq!(cast_row!(i128::MIN => f32), (Ok(-2f32.powi(127)), -2f32.powi(127), -2f32.powi(127)));
+	assert_eq!(cast_row!(u128::MAX => f32), (Err(CastError::Overflow), f32::MAX, f32::INFINITY));
+	assert_eq!(cast_row!(1e300_f64 => f32), (Err(CastError::Overflow), f32::MAX, f32::INFINITY));
+	assert_eq!(cast_row!(-1e300_f64 => f32), (Err(CastError::Underflow), f32::MIN, f32::NEG_INFINITY));
+	assert_eq!(cast_row!(0.1_f64 => f32), (Err(CastError::PrecisionLoss), 0.1, 0.1));
+	assert_eq!(cast_row!(0.5_f64 => f32), (Ok(0.5), 0.5, 0.5));
+	assert_eq!(cast_row!(f64::INFINITY => f32), (Ok(f32::INFINITY), f32::INFINITY, f32::INFINITY));
+	assert!(f64::NAN.try_cast::<f32>().unwrap().is_nan());
+	assert_eq!(0.1_f32.try_cast::<f64>(), Ok(0.1_f32 as f64));
+	
+	check_int_pairs!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+	
+	// the float to integer saturation is `as`
+	for &f in [f64::NAN, f64::INFINITY, -1e40, -1e10, -300.5, -1.5, -0.0, 0.9, 255.5, 65536.0, 3e9, 1e19, 1e40].iter() {
+		assert_eq!(f.saturating_cast::<u8>(), f as u8);
+		assert_eq!(f.saturating_cast::<i32>(), f as i32);
+		assert_eq!(f.saturating_cast::<u64>(), f as u64);
+		assert_eq!(f.saturating_cast::<i128>(), f as i128);
+		// and a checked cast is exact
+		if let Some(i) = f.checked_cast::<i64>() {
+			assert_eq!(i as f64, f);
+		}
+	}
+	
+	match 300.5_f64.try_cast::<u8>() {
+		Ok(v) => println!("300.5 fits in a u8: {}", v),
+		Err(e) => println!("300.5 as u8: {}", e),
+	}
+}
+
 struct FieldStruct {
     first: u8,
     second: u16,
@@ -120,6 +412,103 @@ enum WebEvent2 {
 	Draw { x: u16, y: u16, info: String, color: (u8, u8, u8, u8) }, // max bytes 32
 }
 
+// the fields of (u8, u16, u8) in declaration order: rustc may not reorder them
+#[repr(C)]
+struct ReprCStruct {
+	first: u8,
+	second: u16,
+	third: u8,
+}
+
+// The boxed types of 12_std_lib_types.rs
+struct Point {
+	x: f64,
+	y: f64,
+}
+
+struct Rectangle {
+	top_left: Point,
+	bottom_right: Point,
+	content: &'static str,
+}
+
+// The layout of a sized type: size, alignment, the size of `Option<T>`,
+// and the padding when the fields are known
+struct LayoutInfo {
+	name: &'static str,
+	size: usize,
+	align: usize,
+	option_size: usize,
+	padding: Option<usize>,
+}
+
+impl LayoutInfo {
+	fn of<T>(name: &'static str) -> Self {
+		LayoutInfo {
+			name,
+			size: std::mem::size_of::<T>(),
+			align: std::mem::align_of::<T>(),
+			option_size: std::mem::size_of::<Option<T>>(),
+			padding: None,
+		}
+	}
+	
+	// the bytes not used by any field
+	fn with_fields(mut self, field_sizes: &[usize]) -> Self {
+		let fields: usize = field_sizes.iter().sum();
+		// a wrong field list in `layout_report!`, not a layout to show
+		let padding = self.size.checked_sub(fields).unwrap_or_else(|| {
+			panic!("the fields of {} take {} bytes, more than its size {}", self.name, fields, self.size)
+		});
+		self.padding = Some(padding);
+		self
+	}
+	
+	// `None` is stored in a value T never uses (a null pointer, a char above 0x10FFFF...)
+	fn has_niche(&self) -> bool {
+		self.option_size == self.size
+	}
+}
+
+struct LayoutReport(Vec<LayoutInfo>);
+
+impl LayoutReport {
+	fn get(&self, name: &str) -> Option<&LayoutInfo> {
+		self.0.iter().find(|info| info.name == name)
+	}
+}
+
+impl std::fmt::Display for LayoutReport {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let width = self.0.iter().map(|info| info.name.chars().count()).max().unwrap_or(0).max(4);
+		writeln!(f, "{:<w$} | {:>4} | {:>5} | {:>9} | {:>5} | {:>7}",
+			"type", "size", "align", "Option<T>", "niche", "padding", w = width)?;
+		writeln!(f, "{:-<w$}-+------+-------+-----------+-------+--------", "", w = width)?;
+		for info in &self.0 {
+			let padding = match info.padding {
+				Some(padding) => padding.to_string(),
+				None => String::from("-"),
+			};
+			writeln!(f, "{:<w$} | {:>4} | {:>5} | {:>9} | {:>5} | {:>7}",
+				info.name, info.size, info.align, info.option_size,
+				if info.has_niche() { "yes" } else { "no" }, padding, w = width)?;
+		}
+		Ok(())
+	}
+}
+
+// `layout_report!(u8, FieldStruct => [u8, u16, u8, String])`: the types to report,
+// with the types of their fields after `=>` to get the padding.
+// Only sized types: error[E0277]: the size for values of type `str` cannot be known at compilation time
+macro_rules! layout_report {
+	($($t: ty $(=> [$($field: ty),*])?),* $(,)?) => {
+		LayoutReport(vec![$(
+			LayoutInfo::of::<$t>(stringify!($t))
+				$(.with_fields(&[$(std::mem::size_of::<$field>()),*]))?
+		),*])
+	};
+}
+
 fn literals() {
 	println!("~~~~~~~literals~~~~~~~");
 	let x = 1u8;
@@ -129,52 +518,96 @@ fn literals() {
 	let i = 1;
 	let f = 1.0;
 	
-	//TODO: add new std::mem::size_of for new type
+	// without a suffix an integer is an i32 and a float an f64
+	assert_eq!(std::mem::size_of_val(&x), std::mem::size_of::<u8>());
+	assert_eq!(std::mem::size_of_val(&y), std::mem::size_of::<u32>());
+	assert_eq!(std::mem::size_of_val(&z), std::mem::size_of::<f32>());
+	assert_eq!(std::mem::size_of_val(&i), std::mem::size_of::<i32>());
+	assert_eq!(std::mem::size_of_val(&f), std::mem::size_of::<f64>());
+	
 	println!("base type");
-	println!("size of 'x as u8' in bytes: {}", std::mem::size_of_val(&x));
-	println!("size of 'y as u32' in bytes: {}", std::mem::size_of_val(&y));
-	println!("size of 'z as f32' in bytes: {}", std::mem::size_of_val(&z));
-	println!("size of 'i default' in bytes: {}", std::mem::size_of_val(&i));
-	println!("size of 'f default' in bytes: {}", std::mem::size_of_val(&f));
-		
-	println!("size of '()' in bytes: {}", std::mem::size_of::<()>());
-	// tuple with only one element
-	println!("size of '(u8,)' in bytes: {}", std::mem::size_of::<(u8,)>());
-	println!("size of '&(u8,)' in bytes: {}", std::mem::size_of::<&(u8,)>());
-	println!("size of '(u8, u8, u8)' in bytes: {}", std::mem::size_of::<(u8, u8, u8)>());
-	println!("size of '&(u8, u8, u8)' in bytes: {}", std::mem::size_of::<&(u8, u8, u8)>());
-	println!("size of '[i32; 0]' in bytes: {}", std::mem::size_of::<[i32; 0]>());
-	println!("size of '[i32; 3]' in bytes: {}", std::mem::size_of::<[i32; 3]>());
-	println!("size of '[FieldStruct; 3]' in bytes: {}", std::mem::size_of::<[FieldStruct; 3]>());
-	println!("size of '&[FieldStruct; 3]' in bytes: {}", std::mem::size_of::<&[FieldStruct; 3]>());
+	let base = layout_report!(
+		u8, u16, u32, f32, i32, f64, char, bool,
+		(),
+		// tuple with only one element
+		(u8,) => [u8],
+		&(u8,),
+		(u8, u16, u8) => [u8, u16, u8],
+		ReprCStruct => [u8, u16, u8],
+		&(u8, u16, u8),
+		[i32; 0],
+		[i32; 3] => [i32, i32, i32],
+		[FieldStruct; 3],
+		&[FieldStruct; 3],
+	);
+	print!("{}", base);
 	
 	println!("custom type");
-	//println!("size of 'str' in bytes: {}", std::mem::size_of::<str>());
-	println!("size of '&str' in bytes: {}", std::mem::size_of::<&str>());
-	println!("size of 'String' in bytes: {}", std::mem::size_of::<String>());
-	println!("size of '&String' in bytes: {}", std::mem::size_of::<&String>());
-	println!("size of 'FieldStruct' in bytes: {}", std::mem::size_of::<FieldStruct>());
-	println!("size of '&FieldStruct' in bytes: {}", std::mem::size_of::<&FieldStruct>());
-	println!("size of '&[i32]' in bytes: {}", std::mem::size_of::<&[i32]>());
-	println!("size of '&[FieldStruct]' in bytes: {}", std::mem::size_of::<&[FieldStruct]>());
-	println!("size of 'TupleStruct' in bytes: {}", std::mem::size_of::<TupleStruct>());
-	println!("size of '&TupleStruct' in bytes: {}", std::mem::size_of::<&TupleStruct>());
-	println!("size of 'UnitStruct' in bytes: {}", std::mem::size_of::<UnitStruct>());
-	println!("size of '&UnitStruct' in bytes: {}", std::mem::size_of::<&UnitStruct>());	
-	
-	println!("size of 'WebEvent1' in bytes: {}", std::mem::size_of::<WebEvent1>());
-	// error[E0573]: expected type, found variant `WebEvent::PageLoad`
-	//println!("size of 'WebEvent::PageLoad' in bytes: {}", std::mem::size_of::<WebEvent::PageLoad>());
-	println!("size of '&WebEvent1' in bytes: {}", std::mem::size_of::<&WebEvent1>());
-	println!("size of 'WebEvent2' in bytes: {}", std::mem::size_of::<WebEvent2>());	
-	println!("size of '&WebEvent2' in bytes: {}", std::mem::size_of::<&WebEvent2>());
-	println!("size of 'Vec<i32>' in bytes: {}", std::mem::size_of::<Vec<i32>>());
-	println!("size of '&Vec<i32>' in bytes: {}", std::mem::size_of::<&Vec<i32>>());
+	let custom = layout_report!(
+		&str,
+		String,
+		&String,
+		FieldStruct => [u8, u16, u8, String],
+		&FieldStruct,
+		&[i32],
+		&[FieldStruct],
+		TupleStruct => [u8, u16, u8, String],
+		&TupleStruct,
+		UnitStruct,
+		&UnitStruct,
+		// error[E0573]: expected type, found variant `WebEvent::PageLoad`
+		//WebEvent1::PageLoad,
+		WebEvent1,
+		&WebEvent1,
+		WebEvent2,
+		&WebEvent2,
+		Vec<i32>,
+		&Vec<i32>,
+	);
+	print!("{}", custom);
+	
+	println!("boxed type");
+	let boxed = layout_report!(
+		Point => [f64, f64],
+		Rectangle => [Point, Point, &'static str],
+		Box<Point>,
+		Box<Box<Point>>,
+		Vec<Point>,
+		Box<Vec<&str>>,
+		Option<i32>,
+		Result<i32, std::num::ParseIntError>,
+		Result<i64, &'static str>,
+		std::collections::HashMap<&'static str, &'static str>,
+	);
+	print!("{}", boxed);
 	
 	println!("function type");
-	//println!("size of 'Fn()' in bytes: {}", std::mem::size_of::<Fn()>());
+	// error[E0782]: expected a type, found a trait
+	//let fns = layout_report!(Fn());
+	let fns = layout_report!(fn(usize) -> usize, &dyn Fn(usize) -> usize, Box<dyn Fn(usize) -> usize>);
+	print!("{}", fns);
+	
+	// rustc reorders the fields: u16, u8, u8 has no padding, repr(C) keeps the order
+	assert_eq!(base.get("(u8, u16, u8)").unwrap().padding, Some(0));
+	assert_eq!(base.get("ReprCStruct").unwrap().padding, Some(2));
+	assert_eq!(base.get("u16").unwrap().align, 2);
+	assert_eq!(base.get("[i32; 3]").unwrap().padding, Some(0));
+	// references, Box, Vec and String are never null, char stops at 0x10FFFF, bool uses 0 and 1
+	for name in ["&str", "String", "&FieldStruct", "Vec<i32>"].iter() {
+		assert!(custom.get(name).unwrap().has_niche(), "{}", name);
+	}
+	for name in ["char", "bool", "&(u8,)"].iter() {
+		assert!(base.get(name).unwrap().has_niche(), "{}", name);
+	}
+	assert!(boxed.get("Box<Point>").unwrap().has_niche());
+	assert!(fns.get("fn(usize) -> usize").unwrap().has_niche());
+	// every value of u8, i32 or f64 is valid
+	assert!(!base.get("u8").unwrap().has_niche());
+	assert!(!base.get("i32").unwrap().has_niche());
+	assert!(!base.get("f64").unwrap().has_niche());
 }
 
+// an alias is not a new type: NanoSecond + Inch compiles, `units` in 07_generics.rs rejects it
 type NanoSecond = u64;
 type Inch = u64;
 
@@ -194,6 +627,7 @@ fn aliasing() {
 
 fn main() {
 	casting();
+	num_cast();
 	literals();
 	aliasing();
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 use std::convert::{From, TryFrom, TryInto};
 use std::fmt;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 struct Number {
 	value: i32,
 }
@@ -13,21 +13,143 @@ impl From<i32> for Number {
 	}
 }
 
+// Which rule of which type failed, and on what value
 #[derive(Debug, PartialEq)]
-struct EvenNumber(i32);
+pub struct ValidationError {
+	type_name: &'static str,
+	rule: &'static str,
+	value: String,
+}
+
+impl fmt::Display for ValidationError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}: {} fails the rule \"{}\"", self.type_name, self.value, self.rule)
+	}
+}
+
+impl std::error::Error for ValidationError {}
+
+/*
+`refined!` declares newtypes that only hold a value passing all their rules:
+`Name(raw type) { "rule" => |v| check, ... }`, the rules are checked in order.
+The value is only reachable through `TryFrom`/`FromStr`, then read with `Deref`
+or given back with `Into`. The types live in `refined` so that `Name(v)` can't be
+written outside, the field is private.
+*/
+macro_rules! refined {
+	($($name: ident($raw: ty) { $($rule: expr => $check: expr),+ $(,)? })*) => {
+		$(
+			#[derive(Debug, Clone, PartialEq)]
+			pub struct $name($raw);
+			
+			impl $name {
+				const RULES: &'static [(&'static str, fn(&$raw) -> bool)] = &[$(($rule, $check)),+];
+				
+				pub fn into_inner(self) -> $raw {
+					self.0
+				}
+			}
+			
+			impl TryFrom<$raw> for $name {
+				type Error = ValidationError;
+				
+				fn try_from(v: $raw) -> Result<Self, Self::Error> {
+					match Self::RULES.iter().find(|(_, check)| !check(&v)) {
+						Some((rule, _)) => Err(ValidationError {
+							type_name: stringify!($name),
+							rule,
+							value: format!("{:?}", v),
+						}),
+						None => Ok($name(v)),
+					}
+				}
+			}
+			
+			impl FromStr for $name {
+				type Err = ValidationError;
+				
+				// a text that isn't a $raw at all fails the parse "rule"
+				fn from_str(s: &str) -> Result<Self, Self::Err> {
+					let v = s.parse::<$raw>().map_err(|_| ValidationError {
+						type_name: stringify!($name),
+						rule: concat!("a valid ", stringify!($raw)),
+						value: format!("{:?}", s),
+					})?;
+					$name::try_from(v)
+				}
+			}
+			
+			impl Deref for $name {
+				type Target = $raw;
+				
+				fn deref(&self) -> &$raw {
+					&self.0
+				}
+			}
+			
+			// and `Into<$raw> for $name` with it
+			impl From<$name> for $raw {
+				fn from(v: $name) -> $raw {
+					v.0
+				}
+			}
+			
+			impl fmt::Display for $name {
+				fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+					fmt::Display::fmt(&self.0, f)
+				}
+			}
+		)*
+	};
+}
 
-impl TryFrom<i32> for EvenNumber {
-	type Error = ();
+mod refined {
+	use super::ValidationError;
+	use std::convert::TryFrom;
+	use std::fmt;
+	use std::ops::Deref;
+	use std::str::FromStr;
 	
-	fn try_from(v: i32) -> Result<Self, Self::Error> {
-		if v % 2 == 0 {
-			Ok(EvenNumber(v))
-		} else {
-			Err(())
+	// an email without a regex: one '@' with a name before it and a dotted domain after it
+	fn email_domain(s: &str) -> bool {
+		match s.split_once('@') {
+			Some((_, domain)) => {
+				domain.split('.').count() > 1 && domain.split('.').all(|label| !label.is_empty())
+			}
+			None => false,
+		}
+	}
+	
+	refined! {
+		EvenNumber(i32) {
+			"even" => |v| v % 2 == 0,
+		}
+		Positive(i64) {
+			"positive" => |v| *v > 0,
+		}
+		Percent(u8) {
+			"between 0 and 100" => |v| (0..=100).contains(v),
+		}
+		Month(u32) {
+			"between 1 and 12" => |v| (1..=12).contains(v),
+		}
+		NonEmptyString(String) {
+			"non-empty" => |s| !s.trim().is_empty(),
+		}
+		Email(String) {
+			"non-empty" => |s| !s.is_empty(),
+			"no whitespace" => |s| !s.contains(char::is_whitespace),
+			"one '@'" => |s| s.matches('@').count() == 1,
+			"a name before '@'" => |s| !s.starts_with('@'),
+			"a dotted domain after '@'" => |s| email_domain(s),
 		}
 	}
 }
 
+use refined::{Email, EvenNumber, Month, NonEmptyString, Percent, Positive};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
 struct Circle {
 	radius: i32,
 }
@@ -44,6 +166,285 @@ impl fmt::Display for Circle {
 	}
 }
 
+/*
+`Codec` pairs `Display` and `FromStr` of a type: the text written by `encode` is
+read back by `decode` into an equal value. `round_trip` fuzzes that promise with
+generated values, and damaged texts must give an error or a value that round trips too.
+The parse errors are `ValidationError`s, the rule being the expected format.
+*/
+trait Codec: fmt::Display + FromStr + PartialEq + fmt::Debug {
+	fn encode(&self) -> String {
+		self.to_string()
+	}
+	
+	fn decode(s: &str) -> Result<Self, Self::Err> {
+		s.parse()
+	}
+}
+
+fn format_error(type_name: &'static str, format: &'static str, s: &str) -> ValidationError {
+	ValidationError { type_name, rule: format, value: format!("{:?}", s) }
+}
+
+const CIRCLE_FORMAT: &str = "Circle of radius <i32>";
+
+impl FromStr for Circle {
+	type Err = ValidationError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.strip_prefix("Circle of radius ")
+			.and_then(|radius| radius.parse::<i32>().ok())
+			.map(Circle::from)
+			.ok_or_else(|| format_error("Circle", CIRCLE_FORMAT, s))
+	}
+}
+
+impl Codec for Circle {}
+
+impl fmt::Display for Number {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.value)
+	}
+}
+
+impl FromStr for Number {
+	type Err = ValidationError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.parse::<i32>().map(Number::from).map_err(|_| format_error("Number", "<i32>", s))
+	}
+}
+
+impl Codec for Number {}
+
+// The types of 01_base_output.rs, with their Display
+#[derive(Debug, PartialEq)]
+struct MinMax(i64, i64);
+
+impl fmt::Display for MinMax {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "({}, {})", self.0, self.1)
+	}
+}
+
+impl FromStr for MinMax {
+	type Err = ValidationError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parse = || {
+			let (min, max) = s.strip_prefix('(')?.strip_suffix(')')?.split_once(", ")?;
+			Some(MinMax(min.parse().ok()?, max.parse().ok()?))
+		};
+		parse().ok_or_else(|| format_error("MinMax", "(<i64>, <i64>)", s))
+	}
+}
+
+impl Codec for MinMax {}
+
+// NaN != NaN, a Point2D holding one doesn't round trip
+#[derive(Debug, PartialEq)]
+struct Point2D {
+	x: f64,
+	y: f64,
+}
+
+impl fmt::Display for Point2D {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "x: {}, y: {}", self.x, self.y)
+	}
+}
+
+impl FromStr for Point2D {
+	type Err = ValidationError;
+	
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parse = || {
+			let (x, y) = s.strip_prefix("x: ")?.split_once(", y: ")?;
+			Some(Point2D { x: x.parse().ok()?, y: y.parse().ok()? })
+		};
+		parse().ok_or_else(|| format_error("Point2D", "x: <f64>, y: <f64>", s))
+	}
+}
+
+impl Codec for Point2D {}
+
+#[derive(Debug, PartialEq)]
+struct Color {
+	red: u8,
+	green: u8,
+	blue: u8,
+}
+
+impl fmt::Display for Color {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "RGB ({red}, {green}, {blue}) 0x{red:02X}{green:0>2X}{blue:02X}",
+			red = self.red, green = self.green, blue = self.blue)
+	}
+}
+
+const COLOR_FORMAT: &str = "RGB (<u8>, <u8>, <u8>) 0x<RRGGBB>";
+
+impl FromStr for Color {
+	type Err = ValidationError;
+	
+	// the hex part is written twice, it has to agree with the decimal one
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parse = || {
+			let (decimal, hex) = s.strip_prefix("RGB (")?.split_once(") 0x")?;
+			let mut parts = decimal.split(", ").map(|c| c.parse::<u8>().ok());
+			let color = Color { red: parts.next()??, green: parts.next()??, blue: parts.next()?? };
+			if parts.next().is_some() || hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+				return None;
+			}
+			let rgb = u32::from_str_radix(hex, 16).ok()?;
+			if rgb == (color.red as u32) << 16 | (color.green as u32) << 8 | color.blue as u32 {
+				Some(color)
+			} else {
+				None
+			}
+		};
+		parse().ok_or_else(|| format_error("Color", COLOR_FORMAT, s))
+	}
+}
+
+impl Codec for Color {}
+
+struct Lcg(u64);
+
+impl Lcg {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+		self.0 >> 33
+	}
+	
+	fn below(&mut self, n: u64) -> u64 {
+		self.next() % n
+	}
+	
+	// next() only gives 31 bits
+	fn bits(&mut self) -> u64 {
+		self.next() << 33 | self.next() << 2 | self.below(4)
+	}
+	
+	// the extreme values often, they are the long texts
+	fn i64(&mut self) -> i64 {
+		match self.below(8) {
+			0 => i64::MIN,
+			1 => i64::MAX,
+			2 => 0,
+			3 => self.bits() as i64,
+			_ => self.below(2001) as i64 - 1000,
+		}
+	}
+	
+	fn f64(&mut self) -> f64 {
+		match self.below(8) {
+			0 => [0.0, -0.0, f64::MAX, f64::MIN_POSITIVE, f64::INFINITY, f64::NEG_INFINITY, 1e-300][self.below(7) as usize],
+			1 | 2 => {
+				// any bits but NaN
+				let v = f64::from_bits(self.bits());
+				if v.is_nan() { 1.5 } else { v }
+			}
+			_ => (self.below(20001) as f64 - 10000.0) / 100.0,
+		}
+	}
+}
+
+// How the fuzzer builds a value
+trait Generate {
+	fn generate(rng: &mut Lcg) -> Self;
+}
+
+impl Generate for Circle {
+	fn generate(rng: &mut Lcg) -> Self {
+		Circle::from(rng.i64() as i32)
+	}
+}
+
+impl Generate for Number {
+	fn generate(rng: &mut Lcg) -> Self {
+		Number::from(rng.i64() as i32)
+	}
+}
+
+impl Generate for MinMax {
+	fn generate(rng: &mut Lcg) -> Self {
+		MinMax(rng.i64(), rng.i64())
+	}
+}
+
+impl Generate for Point2D {
+	fn generate(rng: &mut Lcg) -> Self {
+		Point2D { x: rng.f64(), y: rng.f64() }
+	}
+}
+
+impl Generate for Color {
+	fn generate(rng: &mut Lcg) -> Self {
+		Color { red: rng.below(256) as u8, green: rng.below(256) as u8, blue: rng.below(256) as u8 }
+	}
+}
+
+// Returns how many damaged texts were still decoded
+fn round_trip<T>(rng: &mut Lcg, count: usize) -> usize
+	where T: Codec + Generate, T::Err: fmt::Debug {
+	
+	let mut decoded = 0;
+	for _ in 0..count {
+		let value = T::generate(rng);
+		let text = value.encode();
+		match T::decode(&text) {
+			Ok(back) => assert_eq!(back, value, "{:?}", text),
+			Err(e) => panic!("{:?} not decoded: {:?}", text, e),
+		}
+		
+		// a damaged text: one char dropped, doubled or replaced
+		let mut chars: Vec<char> = text.chars().collect();
+		let at = rng.below(chars.len() as u64) as usize;
+		match rng.below(3) {
+			0 => { chars.remove(at); }
+			1 => chars.insert(at, chars[at]),
+			_ => chars[at] = ['0', '9', '-', ' ', ',', 'x', 'F', ')'][rng.below(8) as usize],
+		}
+		let damaged: String = chars.into_iter().collect();
+		if let Ok(other) = T::decode(&damaged) {
+			decoded += 1;
+			assert_eq!(T::decode(&other.encode()).ok(), Some(other), "{:?}", damaged);
+		}
+	}
+	decoded
+}
+
+fn codec_round_trip() {
+	println!("codec_round_trip");
+	
+	let circle = Circle::from(10);
+	assert_eq!(Circle::decode(&circle.encode()), Ok(circle));
+	assert_eq!("Circle of radius -3".parse::<Circle>(), Ok(Circle::from(-3)));
+	let error = "Circle of radius 1.5".parse::<Circle>().unwrap_err();
+	println!("{}", error);
+	assert_eq!(error.rule, CIRCLE_FORMAT);
+	
+	assert_eq!(Number::decode("-7"), Ok(Number::from(-7)));
+	assert_eq!(MinMax::decode("(-300, 300)"), Ok(MinMax(-300, 300)));
+	assert_eq!(Point2D::decode("x: 3.3, y: 7.2"), Ok(Point2D { x: 3.3, y: 7.2 }));
+	assert_eq!(Color::decode("RGB (128, 255, 90) 0x80FF5A"), Ok(Color { red: 128, green: 255, blue: 90 }));
+	// the two halves disagree
+	let error = Color::decode("RGB (128, 255, 90) 0x80FF5B").unwrap_err();
+	println!("{}", error);
+	assert_eq!(error.rule, COLOR_FORMAT);
+	assert!(MinMax::decode("(1, 2").is_err());
+	assert!(Point2D::decode("x: 1, y: NaN").is_ok());
+	
+	let mut rng = Lcg(25);
+	println!("damaged texts still decoded: Circle {}, Number {}, MinMax {}, Point2D {}, Color {}",
+		round_trip::<Circle>(&mut rng, 2000),
+		round_trip::<Number>(&mut rng, 2000),
+		round_trip::<MinMax>(&mut rng, 2000),
+		round_trip::<Point2D>(&mut rng, 2000),
+		round_trip::<Color>(&mut rng, 2000));
+}
+
 fn from_conversion() {
 	println!("from_conversion");
 	let int = 5;
@@ -56,13 +457,87 @@ fn from_conversion() {
 
 fn try_from_conversion() {
 	println!("try_from_conversion");
-	assert_eq!(EvenNumber::try_from(8), Ok(EvenNumber(8)));
-	assert_eq!(EvenNumber::try_from(7), Err(()));
+	assert_eq!(EvenNumber::try_from(8).map(i32::from), Ok(8));
+	assert_eq!(EvenNumber::try_from(7).map(i32::from),
+		Err(ValidationError { type_name: "EvenNumber", rule: "even", value: String::from("7") }));
+	
+	let result: Result<EvenNumber, ValidationError> = 8i32.try_into();
+	assert_eq!(result.map(EvenNumber::into_inner), Ok(8));
+	let result: Result<EvenNumber, ValidationError> = 7i32.try_into();
+	assert_eq!(result.unwrap_err().to_string(), "EvenNumber: 7 fails the rule \"even\"");
+	
+	// error[E0423]: cannot initialize a tuple struct which contains private fields
+	//let odd = EvenNumber(7);
+}
+
+fn refined_types() {
+	println!("refined_types");
+	
+	let even: EvenNumber = "42".parse().unwrap();
+	// Deref: the methods of i32
+	assert_eq!(even.pow(2), 1764);
+	assert_eq!(*even + 1, 43);
+	let raw: i32 = even.into();
+	assert_eq!(raw, 42);
+	
+	assert_eq!(*Positive::try_from(1).unwrap(), 1);
+	assert!(Positive::try_from(0).is_err());
+	assert!(Positive::try_from(i64::MIN).is_err());
+	assert_eq!(*Percent::try_from(100).unwrap(), 100);
+	assert_eq!(*Month::try_from(12).unwrap(), 12);
 	
-	let result: Result<EvenNumber, ()> = 8i32.try_into();
-	assert_eq!(result, Ok(EvenNumber(8)));
-	let result: Result<EvenNumber, ()> = 7i32.try_into();
-	assert_eq!(result, Err(()));
+	let errors = [
+		"43".parse::<EvenNumber>().unwrap_err(),
+		"-3".parse::<Positive>().unwrap_err(),
+		"101".parse::<Percent>().unwrap_err(),
+		// not a u8, before any rule
+		"256".parse::<Percent>().unwrap_err(),
+		"0".parse::<Month>().unwrap_err(),
+		"4.5".parse::<Month>().unwrap_err(),
+		"  ".parse::<NonEmptyString>().unwrap_err(),
+	];
+	for error in errors.iter() {
+		println!("{}", error);
+	}
+	assert_eq!(errors[2].rule, "between 0 and 100");
+	assert_eq!(errors[3], ValidationError { type_name: "Percent", rule: "a valid u8", value: String::from("\"256\"") });
+	assert_eq!(errors[6].to_string(), "NonEmptyString: \"  \" fails the rule \"non-empty\"");
+	
+	let name: NonEmptyString = "ferris".parse().unwrap();
+	// Deref to String, then to str
+	assert_eq!(name.len(), 6);
+	assert!(name.starts_with("fer"));
+	assert_eq!(String::from(name), "ferris");
+	
+	let email: Email = "ferris@rust-lang.org".parse().unwrap();
+	println!("{}", email);
+	assert_eq!(email.split_once('@'), Some(("ferris", "rust-lang.org")));
+	
+	// the first failed rule is the one reported
+	for (text, rule) in [
+		("", "non-empty"),
+		("ferris @rust-lang.org", "no whitespace"),
+		("ferris", "one '@'"),
+		("a@b@rust-lang.org", "one '@'"),
+		("@rust-lang.org", "a name before '@'"),
+		("ferris@localhost", "a dotted domain after '@'"),
+		("ferris@rust-lang.", "a dotted domain after '@'"),
+	] {
+		let error = Email::from_str(text).unwrap_err();
+		println!("{}", error);
+		assert_eq!(error.rule, rule);
+	}
+	
+	// and the error is a std one, `?` boxes it
+	fn total(texts: &[&str]) -> Result<i64, Box<dyn std::error::Error>> {
+		let mut sum = 0;
+		for text in texts {
+			sum += *text.parse::<Positive>()?;
+		}
+		Ok(sum)
+	}
+	assert_eq!(total(&["1", "2", "3"]).unwrap(), 6);
+	assert_eq!(total(&["1", "-2"]).unwrap_err().to_string(), "Positive: -2 fails the rule \"positive\"");
 }
 
 fn to_and_from_string() {
@@ -80,5 +555,7 @@ fn to_and_from_string() {
 fn main() {
 	from_conversion();
 	try_from_conversion();
+	refined_types();
 	to_and_from_string();
+	codec_round_trip();
 }
\ No newline at end of file
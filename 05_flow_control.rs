@@ -192,6 +192,7 @@ fn match_tuple() {
 	}
 }
 
+// the color models are converted into each other in the `color` module of 20_color.rs
 #[allow(dead_code)]
 enum Color {
     // These 3 are specified solely by their name.
@@ -117,6 +117,7 @@ Bounding restricts the generic to types that conform to the bounds.
 Another effect of bounding is that generic instances are allowed to access the methods of traits specified in the bounds.
 T: trait1表示类型T需要声明trait1
 */
+// grown into the `Shape` trait of 25_geometry.rs
 trait HasArea {
 	fn area(&self) -> f64;
 }
@@ -354,11 +355,222 @@ fn phantom_data() {
 }
 
 use std::ops::Add;
-#[derive(Debug, Clone, Copy)]
-enum Inch {}
 
-#[derive(Debug, Clone, Copy)]
-enum Mm {}
+/*
+`Length<Unit>` only keeps apart lengths of different units. `units` goes further:
+every unit has a dimension (length, time, mass or a derived one) and a scale to the SI unit,
+so that quantities of the same dimension convert into each other, and `*` and `/` give
+the derived dimension: Inch / NanoSecond is a velocity, Kilogram * acceleration is a force.
+Adding a NanoSecond to an Inch doesn't compile any more, unlike the `u64` aliases of 03_types.rs.
+*/
+mod units {
+	use std::fmt;
+	use std::marker::PhantomData;
+	use std::ops::{Add, Div, Mul, Sub};
+	
+	// The dimensions, marker types only
+	pub mod dim {
+		#[derive(Debug)] pub enum Length {}
+		#[derive(Debug)] pub enum Time {}
+		#[derive(Debug)] pub enum Mass {}
+		#[derive(Debug)] pub enum Area {}
+		#[derive(Debug)] pub enum Velocity {}
+		#[derive(Debug)] pub enum Acceleration {}
+		#[derive(Debug)] pub enum Force {}
+		// length / length...
+		#[derive(Debug)] pub enum Ratio {}
+	}
+	
+	// The dimension of `Self * Rhs` and of `Self / Rhs`
+	pub trait DimMul<Rhs> {
+		type Output;
+	}
+	
+	pub trait DimDiv<Rhs> {
+		type Output;
+	}
+	
+	// Stable Rust can't add exponents at the type level, the products and quotients are listed
+	macro_rules! dimension_rules {
+		($($a: ident $op: tt $b: ident = $c: ident),* $(,)?) => {
+			$(dimension_rules!(@rule $a $op $b = $c);)*
+		};
+		(@rule $a: ident * $b: ident = $c: ident) => {
+			impl DimMul<dim::$b> for dim::$a {
+				type Output = dim::$c;
+			}
+		};
+		(@rule $a: ident / $b: ident = $c: ident) => {
+			impl DimDiv<dim::$b> for dim::$a {
+				type Output = dim::$c;
+			}
+		};
+	}
+	
+	dimension_rules! {
+		Length * Length = Area,
+		Area / Length = Length,
+		Length / Time = Velocity,
+		Velocity * Time = Length,
+		Time * Velocity = Length,
+		Velocity / Time = Acceleration,
+		Acceleration * Time = Velocity,
+		Mass * Acceleration = Force,
+		Acceleration * Mass = Force,
+		Force / Mass = Acceleration,
+		Force / Acceleration = Mass,
+		Length / Length = Ratio,
+		Time / Time = Ratio,
+		Mass / Mass = Ratio,
+	}
+	
+	pub trait Unit {
+		type Dim;
+		// how many SI units (m, s, kg) one of this unit is
+		const SCALE: f64;
+		
+		fn symbol() -> String;
+	}
+	
+	macro_rules! base_units {
+		($($name: ident: $dim: ident, $scale: expr, $symbol: expr;)*) => {
+			$(
+				// Copy for the derives of `Length<Unit>`
+				#[derive(Debug, Clone, Copy)]
+				pub enum $name {}
+				
+				impl Unit for $name {
+					type Dim = dim::$dim;
+					const SCALE: f64 = $scale;
+					
+					fn symbol() -> String {
+						String::from($symbol)
+					}
+				}
+			)*
+		};
+	}
+	
+	base_units! {
+		Meter: Length, 1.0, "m";
+		Mm: Length, 0.001, "mm";
+		Inch: Length, 0.0254, "in";
+		Foot: Length, 0.3048, "ft";
+		Yard: Length, 0.9144, "yd";
+		Mile: Length, 1609.344, "mi";
+		Kilometer: Length, 1000.0, "km";
+		Second: Time, 1.0, "s";
+		NanoSecond: Time, 1e-9, "ns";
+		Kilogram: Mass, 1.0, "kg";
+		Gram: Mass, 0.001, "g";
+	}
+	
+	// The unit A·B and the unit A/B
+	pub struct Prod<A, B>(PhantomData<(A, B)>);
+	pub struct Per<A, B>(PhantomData<(A, B)>);
+	
+	impl<A: Unit, B: Unit> Unit for Prod<A, B> where A::Dim: DimMul<B::Dim> {
+		type Dim = <A::Dim as DimMul<B::Dim>>::Output;
+		const SCALE: f64 = A::SCALE * B::SCALE;
+		
+		fn symbol() -> String {
+			format!("{}·{}", A::symbol(), B::symbol())
+		}
+	}
+	
+	impl<A: Unit, B: Unit> Unit for Per<A, B> where A::Dim: DimDiv<B::Dim> {
+		type Dim = <A::Dim as DimDiv<B::Dim>>::Output;
+		const SCALE: f64 = A::SCALE / B::SCALE;
+		
+		fn symbol() -> String {
+			format!("{}/{}", A::symbol(), B::symbol())
+		}
+	}
+	
+	pub type MeterPerSecond = Per<Meter, Second>;
+	pub type MeterPerSecond2 = Per<MeterPerSecond, Second>;
+	pub type Newton = Prod<Kilogram, MeterPerSecond2>;
+	
+	// `Length<Unit>` for any unit
+	pub struct Quantity<U>(f64, PhantomData<U>);
+	
+	// derive would require U: Clone, a marker type doesn't need to be
+	impl<U> Clone for Quantity<U> {
+		fn clone(&self) -> Self {
+			*self
+		}
+	}
+	
+	impl<U> Copy for Quantity<U> {}
+	
+	impl<U> Quantity<U> {
+		pub fn new(value: f64) -> Self {
+			Quantity(value, PhantomData)
+		}
+		
+		pub fn value(&self) -> f64 {
+			self.0
+		}
+	}
+	
+	impl<U: Unit> Quantity<U> {
+		// only to a unit of the same dimension
+		pub fn convert<V: Unit<Dim = U::Dim>>(self) -> Quantity<V> {
+			Quantity::new(self.0 * U::SCALE / V::SCALE)
+		}
+	}
+	
+	// the same unit only, convert first otherwise
+	impl<U> Add for Quantity<U> {
+		type Output = Quantity<U>;
+		
+		fn add(self, rhs: Self) -> Self::Output {
+			Quantity::new(self.0 + rhs.0)
+		}
+	}
+	
+	impl<U> Sub for Quantity<U> {
+		type Output = Quantity<U>;
+		
+		fn sub(self, rhs: Self) -> Self::Output {
+			Quantity::new(self.0 - rhs.0)
+		}
+	}
+	
+	impl<A: Unit, B: Unit> Mul<Quantity<B>> for Quantity<A> where A::Dim: DimMul<B::Dim> {
+		type Output = Quantity<Prod<A, B>>;
+		
+		fn mul(self, rhs: Quantity<B>) -> Self::Output {
+			Quantity::new(self.0 * rhs.0)
+		}
+	}
+	
+	impl<A: Unit, B: Unit> Div<Quantity<B>> for Quantity<A> where A::Dim: DimDiv<B::Dim> {
+		type Output = Quantity<Per<A, B>>;
+		
+		fn div(self, rhs: Quantity<B>) -> Self::Output {
+			Quantity::new(self.0 / rhs.0)
+		}
+	}
+	
+	impl<U: Unit> fmt::Display for Quantity<U> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			fmt::Display::fmt(&self.0, f)?;
+			write!(f, " {}", U::symbol())
+		}
+	}
+	
+	impl<U: Unit> fmt::Debug for Quantity<U> {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			write!(f, "Quantity({:?} {})", self.0, U::symbol())
+		}
+	}
+}
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Div, Mul, Sub};
+use units::{Foot, Inch, Kilometer, Meter, Mile, Mm, Yard};
 
 #[derive(Debug, Clone, Copy)]
 struct Length<Unit>(f64, PhantomData<Unit>);
@@ -371,6 +583,66 @@ impl<Unit> Add for Length<Unit> {
 	}
 }
 
+impl<Unit> Sub for Length<Unit> {
+	type Output = Length<Unit>;
+	
+	fn sub(self, rhs: Self::Output) -> Self::Output {
+		Length(self.0 - rhs.0, PhantomData)
+	}
+}
+
+// scaling keeps the unit
+impl<Unit> Mul<f64> for Length<Unit> {
+	type Output = Length<Unit>;
+	
+	fn mul(self, rhs: f64) -> Self::Output {
+		Length(self.0 * rhs, PhantomData)
+	}
+}
+
+impl<Unit> Div<f64> for Length<Unit> {
+	type Output = Length<Unit>;
+	
+	fn div(self, rhs: f64) -> Self::Output {
+		Length(self.0 / rhs, PhantomData)
+	}
+}
+
+// derive would require Unit: PartialOrd
+impl<Unit> PartialEq for Length<Unit> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<Unit> PartialOrd for Length<Unit> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		self.0.partial_cmp(&other.0)
+	}
+}
+
+impl<U: units::Unit> fmt::Display for Length<U> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&self.0, f)?;
+		write!(f, " {}", U::symbol())
+	}
+}
+
+trait ConvertTo<T> {
+	fn convert_to(self) -> T;
+}
+
+// through the SI unit: value * from::SCALE / to::SCALE
+impl<A, B> ConvertTo<Length<B>> for Length<A>
+where
+	A: units::Unit<Dim = units::dim::Length>,
+	B: units::Unit<Dim = units::dim::Length>,
+{
+	fn convert_to(self) -> Length<B> {
+		Length(self.0 * A::SCALE / B::SCALE, PhantomData)
+	}
+}
+
 fn phantom_use() {
 	println!("~~~~~~~Phantom use~~~~~~~");
 	
@@ -388,6 +660,153 @@ fn phantom_use() {
 	//let one_feter = one_foot + one_meter;
 }
 
+fn length_conversion() {
+	println!("~~~~~~~length conversion~~~~~~~");
+	
+	let one_foot: Length<Foot> = Length(1.0, PhantomData);
+	let in_mm: Length<Mm> = one_foot.convert_to();
+	println!("{} = {}", one_foot, in_mm);
+	assert_eq!(in_mm.to_string(), "304.8 mm");
+	
+	// the target is told by the annotation, or by the turbofish on the trait
+	let in_inches = ConvertTo::<Length<Inch>>::convert_to(one_foot);
+	assert!((in_inches.0 - 12.0).abs() < 1e-9);
+	
+	let one_mile: Length<Mile> = Length(1.0, PhantomData);
+	let in_km: Length<Kilometer> = one_mile.convert_to();
+	let in_yards: Length<Yard> = one_mile.convert_to();
+	println!("{} = {:.3} = {:.0}", one_mile, in_km, in_yards);
+	assert_eq!(format!("{:.3}", in_km), "1.609 km");
+	assert!((in_yards.0 - 1760.0).abs() < 1e-9);
+	
+	// a round trip gives the value back
+	let marathon: Length<Kilometer> = Length(42.195, PhantomData);
+	let back: Length<Kilometer> = ConvertTo::<Length<Meter>>::convert_to(marathon).convert_to();
+	assert!((back.0 - marathon.0).abs() < 1e-12);
+	
+	// mixed units are added once converted
+	let total = one_foot.convert_to() + Length::<Mm>(100.0, PhantomData);
+	println!("{} + 100 mm = {:.1}", one_foot, total);
+	assert!((total.0 - 404.8).abs() < 1e-9);
+	
+	let yard: Length<Foot> = Length::<Yard>(1.0, PhantomData).convert_to();
+	assert!(((yard - one_foot).0 - 2.0).abs() < 1e-9);
+	assert!(((one_foot * 3.0).0 - yard.0).abs() < 1e-9);
+	assert_eq!((Length::<Meter>(10.0, PhantomData) / 4.0).to_string(), "2.5 m");
+	
+	// compared within a unit
+	assert!(one_foot < yard);
+	assert!(in_mm > Length(300.0, PhantomData));
+	assert_eq!(Length::<Mm>(f64::NAN, PhantomData).partial_cmp(&in_mm), None);
+	
+	// error[E0308]: mismatched types
+	//let wrong = one_foot < in_mm;
+	// only between lengths
+	// error[E0271]: type mismatch resolving `<NanoSecond as Unit>::Dim == Length`
+	//let wrong: Length<units::NanoSecond> = one_foot.convert_to();
+}
+
+fn dimensional_analysis() {
+	println!("~~~~~~~dimensional analysis~~~~~~~");
+	use units::*;
+	
+	let nanoseconds: Quantity<NanoSecond> = Quantity::new(5.0);
+	let inches: Quantity<Inch> = Quantity::new(2.0);
+	
+	// compile_fail_checks() makes rustc reject these three lines with their error codes
+	// error[E0308]: mismatched types, expected `Quantity<NanoSecond>`, found `Quantity<Inch>`
+	//let unit = nanoseconds + inches;
+	// error[E0271]: type mismatch resolving `<NanoSecond as Unit>::Dim == Length`
+	//let unit = nanoseconds + inches.convert::<NanoSecond>();
+	// no rule for mass * mass, the only one left is mass * acceleration
+	// error[E0271]: type mismatch resolving `<Gram as Unit>::Dim == Acceleration`
+	//let unit = Quantity::<Gram>::new(1.0) * Quantity::<Gram>::new(1.0);
+	
+	// a conversion within a dimension
+	let millimeters = inches.convert::<Mm>();
+	println!("{} = {}", inches, millimeters);
+	assert!((millimeters.value() - 50.8).abs() < 1e-9);
+	assert!((millimeters.convert::<Inch>().value() - 2.0).abs() < 1e-12);
+	let total = inches + Quantity::<Mm>::new(25.4).convert();
+	assert!((total.value() - 3.0).abs() < 1e-12);
+	
+	// length / time is a velocity, whatever the units
+	let speed = inches / nanoseconds;
+	let si_speed = speed.convert::<MeterPerSecond>();
+	println!("{} = {:.0}", speed, si_speed);
+	assert!((si_speed.value() - 10_160_000.0).abs() < 1e-6);
+	
+	// velocity * time is a length again
+	let distance = si_speed * Quantity::<Second>::new(2.0);
+	let distance: Quantity<Meter> = distance.convert();
+	println!("in 2 s: {:.0}", distance);
+	assert!((distance.value() - 20_320_000.0).abs() < 1e-6);
+	
+	let area = Quantity::<Meter>::new(3.0) * Quantity::<Mm>::new(500.0);
+	let area_m2: Quantity<Prod<Meter, Meter>> = area.convert();
+	println!("{} = {}", area, area_m2);
+	assert_eq!(area_m2.value(), 1.5);
+	assert_eq!((area / Quantity::<Meter>::new(3.0)).convert::<Mm>().value(), 500.0);
+	
+	// F = m·a
+	let g: Quantity<MeterPerSecond2> = Quantity::new(9.81);
+	let weight = Quantity::<Gram>::new(2000.0) * g;
+	let newtons: Quantity<Newton> = weight.convert();
+	println!("{} = {:.2}", weight, newtons);
+	assert!((newtons.value() - 19.62).abs() < 1e-9);
+	
+	// a ratio has no dimension left
+	let ratio = (Quantity::<Meter>::new(1.0) / inches).convert::<Per<Inch, Inch>>();
+	assert!((ratio.value() - 1.0 / 0.0508).abs() < 1e-9);
+}
+
+// The commented out lines of dimensional_analysis() are compile-fail tests: each one is
+// uncommented into a copy of this file, which rustc must reject with the error code above it.
+// The unchanged copy must compile, so the error comes from the line.
+fn compile_fail_checks() {
+	println!("~~~~~~~compile-fail checks~~~~~~~");
+	use std::process::{Command, Output};
+	
+	const SOURCE: &str = include_str!("07_generics.rs");
+	let cases = [
+		("let unit = nanoseconds + inches;", "E0308"),
+		("let unit = nanoseconds + inches.convert::<NanoSecond>();", "E0271"),
+		("let unit = Quantity::<Gram>::new(1.0) * Quantity::<Gram>::new(1.0);", "E0271"),
+	];
+	
+	let dir = std::env::temp_dir().join(format!("07_generics_compile_fail_{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let rustc = std::env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+	let compile = |name: &str, source: &str| -> Option<Output> {
+		let path = dir.join(format!("{}.rs", name));
+		std::fs::write(&path, source).unwrap();
+		Command::new(&rustc)
+			.args(["--edition", "2021", "--crate-type", "bin", "--emit", "metadata", "--out-dir"])
+			.arg(&dir)
+			.arg(&path)
+			.output()
+			.ok()
+	};
+	
+	// the copies next to it include_str! it as well
+	match compile("07_generics", SOURCE) {
+		None => println!("{} not found, the compile-fail checks are skipped", rustc),
+		Some(output) => {
+			assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+			for (i, (line, code)) in cases.iter().enumerate() {
+				let commented = format!("//{}", line);
+				assert_eq!(SOURCE.matches(&commented).count(), 1, "{}", commented);
+				let output = compile(&format!("case_{}", i), &SOURCE.replacen(&commented, line, 1)).unwrap();
+				let stderr = String::from_utf8_lossy(&output.stderr);
+				assert!(!output.status.success(), "{} compiles", line);
+				assert!(stderr.contains(&format!("error[{}]", code)), "{} fails without {}:\n{}", line, code, stderr);
+				println!("{} -> error[{}]", line, code);
+			}
+		}
+	}
+	std::fs::remove_dir_all(&dir).unwrap();
+}
+
 fn main() {
 	functions();
 	impl_func();
@@ -403,4 +822,7 @@ fn main() {
 	
 	phantom_data();
 	phantom_use();
+	length_conversion();
+	dimensional_analysis();
+	compile_fail_checks();
 }
\ No newline at end of file
@@ -295,6 +295,186 @@ fn trait_iterator() {
 	}
 }
 
+/*
+`Fibonacci` above panics (debug) or wraps around (release) after 47 terms of u32.
+`Recurrence` generalizes it to any linear recurrence of order N over any numeric type:
+	a(n) = c[0] * a(n-N) + c[1] * a(n-N+1) + ... + c[N-1] * a(n-1)
+The arithmetic is checked: the iterator ends (returns `None`) instead of overflowing.
+*/
+trait CheckedNum: Copy {
+	const ZERO: Self;
+	const ONE: Self;
+	
+	fn checked_add(self, rhs: Self) -> Option<Self>;
+	fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+// the integer types already have inherent checked_add/checked_mul,
+// inherent methods take precedence over the trait ones, so this isn't a recursive call
+macro_rules! impl_checked_num_for_int {
+	($($t: ty),*) => {
+		$(
+			impl CheckedNum for $t {
+				const ZERO: Self = 0;
+				const ONE: Self = 1;
+				
+				fn checked_add(self, rhs: Self) -> Option<Self> {
+					<$t>::checked_add(self, rhs)
+				}
+				
+				fn checked_mul(self, rhs: Self) -> Option<Self> {
+					<$t>::checked_mul(self, rhs)
+				}
+			}
+		)*
+	};
+}
+
+impl_checked_num_for_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// floats don't panic or wrap, they overflow to infinity (or NaN)
+macro_rules! impl_checked_num_for_float {
+	($($t: ty),*) => {
+		$(
+			impl CheckedNum for $t {
+				const ZERO: Self = 0.0;
+				const ONE: Self = 1.0;
+				
+				fn checked_add(self, rhs: Self) -> Option<Self> {
+					Some(self + rhs).filter(|v| v.is_finite())
+				}
+				
+				fn checked_mul(self, rhs: Self) -> Option<Self> {
+					Some(self * rhs).filter(|v| v.is_finite())
+				}
+			}
+		)*
+	};
+}
+
+impl_checked_num_for_float!(f32, f64);
+
+struct Recurrence<T, const N: usize> {
+	// the next N terms, terms[0] is returned by the next call of `next`
+	terms: [T; N],
+	coefficients: [T; N],
+	// Some(n) once computing a new term overflowed: only n terms are left in `terms`
+	remaining: Option<usize>,
+}
+
+impl<T: CheckedNum, const N: usize> Recurrence<T, N> {
+	fn new(initial: [T; N], coefficients: [T; N]) -> Self {
+		assert!(N > 0, "a recurrence needs at least one initial term");
+		Recurrence { terms: initial, coefficients, remaining: None }
+	}
+	
+	fn next_term(&self) -> Option<T> {
+		self.terms.iter()
+			.zip(self.coefficients.iter())
+			.try_fold(T::ZERO, |sum, (&term, &c)| sum.checked_add(term.checked_mul(c)?))
+	}
+}
+
+// 0, 1, 1, 2, 3, 5, 8, ...
+fn fibonacci_of<T: CheckedNum>() -> Recurrence<T, 2> {
+	Recurrence::new([T::ZERO, T::ONE], [T::ONE, T::ONE])
+}
+
+// 2, 1, 3, 4, 7, 11, ...
+fn lucas<T: CheckedNum>() -> Recurrence<T, 2> {
+	let two = T::ONE.checked_add(T::ONE).expect("2 fits in every numeric type");
+	Recurrence::new([two, T::ONE], [T::ONE, T::ONE])
+}
+
+// 0, 0, 1, 1, 2, 4, 7, 13, ...
+fn tribonacci<T: CheckedNum>() -> Recurrence<T, 3> {
+	Recurrence::new([T::ZERO, T::ZERO, T::ONE], [T::ONE; 3])
+}
+
+impl<T: CheckedNum, const N: usize> Iterator for Recurrence<T, N> {
+	type Item = T;
+	
+	fn next(&mut self) -> Option<Self::Item> {
+		let next = match self.remaining {
+			Some(0) => return None,
+			Some(n) => {
+				self.remaining = Some(n - 1);
+				None
+			}
+			None => {
+				let next = self.next_term();
+				if next.is_none() {
+					// the terms already computed are still valid, hand them out first
+					self.remaining = Some(N - 1);
+				}
+				next
+			}
+		};
+		
+		let current = self.terms[0];
+		self.terms.rotate_left(1);
+		if let Some(next) = next {
+			self.terms[N - 1] = next;
+		}
+		Some(current)
+	}
+	
+	// the terms in the window are always returned, beyond that we only know
+	// the upper bound once an overflow happened
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match self.remaining {
+			Some(n) => (n, Some(n)),
+			None => (N, None),
+		}
+	}
+}
+
+fn trait_recurrence() {
+	print_knowledage_point("trait recurrence");
+	
+	let fib: Vec<u32> = fibonacci_of().take(10).collect();
+	println!("Fibonacci: {:?}", fib);
+	assert_eq!(fib, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+	
+	let lucas: Vec<i64> = lucas().take(6).collect();
+	println!("Lucas: {:?}", lucas);
+	assert_eq!(lucas, vec![2, 1, 3, 4, 7, 11]);
+	
+	let trib: Vec<u8> = tribonacci().take(8).collect();
+	println!("Tribonacci: {:?}", trib);
+	assert_eq!(trib, vec![0, 0, 1, 1, 2, 4, 7, 13]);
+	
+	// Pell numbers: a(n) = a(n-2) + 2 * a(n-1)
+	let pell: Vec<u16> = Recurrence::new([0, 1], [1, 2]).take(6).collect();
+	assert_eq!(pell, vec![0, 1, 2, 5, 12, 29]);
+	
+	// negative coefficients work for signed types: a(n) = a(n-1) - a(n-2)
+	let periodic: Vec<i8> = Recurrence::new([1, 2], [-1, 1]).take(7).collect();
+	assert_eq!(periodic, vec![1, 2, 1, -1, -2, -1, 1]);
+	
+	// instead of overflowing, the sequence ends after the largest representable term
+	assert_eq!(fibonacci_of::<u8>().count(), 14);
+	assert_eq!(fibonacci_of::<u8>().last(), Some(233));
+	assert_eq!(fibonacci_of::<u32>().count(), 48);
+	assert_eq!(fibonacci_of::<u32>().last(), Some(2_971_215_073));
+	assert_eq!(fibonacci_of::<u64>().last(), Some(12_200_160_415_121_876_738));
+	assert_eq!(fibonacci_of::<u128>().count(), 187);
+	// f64 stops before reaching infinity
+	assert!(fibonacci_of::<f64>().last().unwrap().is_finite());
+	println!("{} Fibonacci numbers fit in a u32", fibonacci_of::<u32>().count());
+	
+	let fib = fibonacci_of::<u8>();
+	assert_eq!(fib.size_hint(), (2, None));
+	let mut fib = fib.skip(12);
+	assert_eq!(fib.next(), Some(144));
+	// computing 377 overflowed, only 233 is left
+	assert_eq!(fib.size_hint(), (1, Some(1)));
+	assert_eq!(fib.next(), Some(233));
+	assert_eq!(fib.size_hint(), (0, Some(0)));
+	assert_eq!(fib.next(), None);
+	assert_eq!(fib.next(), None);
+}
+
 /*
 impl Trait can be used in two locations:
 
@@ -467,6 +647,7 @@ fn main() {
 	trait_as_return();
 	trait_op_overload();
 	trait_iterator();
+	trait_recurrence();
 	trait_clone();
 	trait_superset();
 	trait_fn_with_same_name();
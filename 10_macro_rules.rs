@@ -90,10 +90,168 @@ fn macro_repeat() {
     println!("{}", find_min!(5u32, 2u32 * 3, 4u32));
 }
 
+// The enums of 02_custom_types.rs can't list their variants, map a discriminant back
+// to a variant or give a variant's name. `reflect_enum!` wraps the declaration of
+// such a C-like enum and generates `COUNT`, `ALL`, `name()`, `TryFrom<i32>` and `FromStr`.
+// `$(= $value: expr)?` makes the explicit discriminant optional.
+// The enum is `#[repr(i32)]`, so a discriminant outside of i32 doesn't compile instead of
+// wrapping in `as i32` and matching another value.
+#[derive(Debug, PartialEq)]
+pub struct InvalidDiscriminant {
+	enum_name: &'static str,
+	value: i32,
+}
+
+impl std::fmt::Display for InvalidDiscriminant {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{} has no variant with discriminant {}", self.enum_name, self.value)
+	}
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UnknownVariant {
+	enum_name: &'static str,
+	name: String,
+}
+
+impl std::fmt::Display for UnknownVariant {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{} has no variant named {:?}", self.enum_name, self.name)
+	}
+}
+
+macro_rules! reflect_enum {
+	(
+		$(#[$meta: meta])*
+		$vis: vis enum $name: ident {
+			$($(#[$variant_meta: meta])* $variant: ident $(= $value: expr)?),* $(,)?
+		}
+	) => {
+		$(#[$meta])*
+		#[repr(i32)]
+		$vis enum $name {
+			$($(#[$variant_meta])* $variant $(= $value)?),*
+		}
+		
+		impl $name {
+			$vis const COUNT: usize = [$(stringify!($variant)),*].len();
+			// in declaration order
+			$vis const ALL: [$name; $name::COUNT] = [$($name::$variant),*];
+			
+			$vis fn name(&self) -> &'static str {
+				match self {
+					$($name::$variant => stringify!($variant)),*
+				}
+			}
+		}
+		
+		impl std::convert::TryFrom<i32> for $name {
+			type Error = $crate::InvalidDiscriminant;
+			
+			fn try_from(value: i32) -> Result<Self, Self::Error> {
+				match value {
+					$(v if v == $name::$variant as i32 => Ok($name::$variant),)*
+					_ => Err($crate::InvalidDiscriminant { enum_name: stringify!($name), value }),
+				}
+			}
+		}
+		
+		impl std::str::FromStr for $name {
+			type Err = $crate::UnknownVariant;
+			
+			// the exact variant name, as `name()` returns it
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				match s {
+					$(stringify!($variant) => Ok($name::$variant),)*
+					_ => Err($crate::UnknownVariant { enum_name: stringify!($name), name: s.to_string() }),
+				}
+			}
+		}
+	};
+}
+
+reflect_enum! {
+	// enum with implicit discriminator (starts at 0)
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	enum Number {
+		Zero,
+		One,
+		Two,
+	}
+}
+
+reflect_enum! {
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	enum Color {
+		Red = 0xff0000,
+		Green = 0x00ff00,
+		Blue = 0x0000ff,
+	}
+}
+
+// an error[E0081]: discriminant value `1` assigned more than once, as for a plain enum
+//reflect_enum! { enum Twice { A = 1, B = 1 } }
+// error: literal out of range for `i32`, instead of `Huge as i32 == 0`
+//reflect_enum! { enum Wide { Huge = 0x1_0000_0000 } }
+
+// `$vis` goes on the generated items too, they can be used outside of the module
+mod signal {
+	reflect_enum! {
+		#[derive(Debug, Clone, Copy, PartialEq)]
+		pub enum Light {
+			Red,
+			Yellow,
+			Green,
+		}
+	}
+}
+
+fn macro_reflection() {
+	println!("~~~~~~~macro reflection~~~~~~~");
+	
+	use std::convert::TryFrom;
+	
+	for color in Color::ALL.iter() {
+		println!("{} is 0x{:06x}", color.name(), *color as i32);
+	}
+	println!("{} has {} variants: {:?}", stringify!(Number), Number::COUNT, Number::ALL);
+	
+	assert_eq!(Number::COUNT, 3);
+	assert_eq!(Number::ALL, [Number::Zero, Number::One, Number::Two]);
+	assert_eq!(Color::ALL.iter().map(Color::name).collect::<Vec<_>>(), ["Red", "Green", "Blue"]);
+	
+	assert_eq!(Number::try_from(2), Ok(Number::Two));
+	assert_eq!(Color::try_from(0x00ff00), Ok(Color::Green));
+	assert_eq!(Number::try_from(3), Err(InvalidDiscriminant { enum_name: "Number", value: 3 }));
+	assert_eq!(Color::try_from(1), Err(InvalidDiscriminant { enum_name: "Color", value: 1 }));
+	
+	assert_eq!("One".parse(), Ok(Number::One));
+	assert_eq!("Blue".parse(), Ok(Color::Blue));
+	let unknown = "blue".parse::<Color>().unwrap_err();
+	println!("{}", unknown);
+	assert_eq!(unknown, UnknownVariant { enum_name: "Color", name: String::from("blue") });
+	
+	// every variant survives the name and the discriminant
+	for &number in Number::ALL.iter() {
+		assert_eq!(number.name().parse(), Ok(number));
+		assert_eq!(Number::try_from(number as i32), Ok(number));
+	}
+	for &color in Color::ALL.iter() {
+		assert_eq!(color.name().parse(), Ok(color));
+		assert_eq!(Color::try_from(color as i32), Ok(color));
+	}
+	
+	use signal::Light;
+	assert_eq!(Light::COUNT, 3);
+	assert_eq!(Light::ALL.iter().map(Light::name).collect::<Vec<_>>(), ["Red", "Yellow", "Green"]);
+	assert_eq!(Light::try_from(1), Ok(Light::Yellow));
+}
+
 fn main() {
 	macro_simple();
 	
 	macro_designators();
 	macro_overload();
 	macro_repeat();
+	macro_reflection();
 }
\ No newline at end of file
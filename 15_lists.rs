@@ -1,3 +1,5 @@
+use std::fmt;
+use std::iter::FromIterator;
 use std::mem;
 
 /*
@@ -145,6 +147,710 @@ impl<'a, T> Iterator for Iter<'a, T> {
 	}
 }
 
+struct IterMut<'a, T> {
+	next: Option<&'a mut Node<T>>
+}
+
+impl<T> List<T> {
+	fn iter_mut(&mut self) -> IterMut<'_, T> {
+		IterMut {
+			next: self.head.as_deref_mut(),
+		}
+	}
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+	type Item = &'a mut T;
+	
+	fn next(&mut self) -> Option<Self::Item> {
+		// &mut T不支持Copy特性, 必须先take()出来, 否则next字段会被借用两次
+		// error[E0507]: cannot move out of `self.next` which is behind a mutable reference
+		//self.next.map(|node| {
+		self.next.take().map(|node| {
+			self.next = node.next.as_deref_mut();
+			&mut node.elem
+		})
+	}
+}
+
+impl<T> IntoIterator for List<T> {
+	type Item = T;
+	type IntoIter = IntoIter<T>;
+	
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter(self)
+	}
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+	type Item = &'a T;
+	type IntoIter = Iter<'a, T>;
+	
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+	type Item = &'a mut T;
+	type IntoIter = IterMut<'a, T>;
+	
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter_mut()
+	}
+}
+
+impl<T> List<T> {
+	fn is_empty(&self) -> bool {
+		self.head.is_none()
+	}
+	
+	// O(n), the list doesn't cache its length
+	fn len(&self) -> usize {
+		self.iter().count()
+	}
+	
+	// Returns the `next` link of the last node (or `head` for an empty list),
+	// i.e. the place where a new node should be hung to append it.
+	fn tail_link(&mut self) -> &mut Link<T> {
+		let mut cur_link = &mut self.head;
+		while let Some(boxed_node) = cur_link {
+			cur_link = &mut boxed_node.next;
+		}
+		cur_link
+	}
+	
+	// Reverses the list in place without allocating
+	fn reverse(&mut self) {
+		let mut reversed: Link<T> = None;
+		let mut cur_link = self.head.take();
+		while let Some(mut boxed_node) = cur_link {
+			cur_link = mem::replace(&mut boxed_node.next, reversed);
+			reversed = Some(boxed_node);
+		}
+		self.head = reversed;
+	}
+	
+	// Moves all nodes of `other` to the end of `self`, leaving `other` empty
+	fn append(&mut self, other: &mut List<T>) {
+		let other_head = other.head.take();
+		*self.tail_link() = other_head;
+	}
+	
+	// Splits the list into two at the given index. Returns everything after the given index, including the index.
+	// Panics if `at > len`.
+	fn split_off(&mut self, at: usize) -> List<T> {
+		let mut cur_link = &mut self.head;
+		for _ in 0..at {
+			cur_link = match cur_link {
+				Some(boxed_node) => &mut boxed_node.next,
+				None => panic!("Cannot split off at a nonexistent index"),
+			};
+		}
+		
+		List {
+			head: cur_link.take(),
+		}
+	}
+	
+	// Retains only the elements specified by the predicate, keeping their order
+	fn retain<F>(&mut self, mut f: F)
+		where F: FnMut(&T) -> bool {
+		
+		let mut cur_link = &mut self.head;
+		while cur_link.is_some() {
+			if f(&cur_link.as_ref().unwrap().elem) {
+				cur_link = &mut cur_link.as_mut().unwrap().next;
+			} else {
+				// unlink the node, the removed Box<Node<T>> is dropped here
+				let next = cur_link.as_mut().unwrap().next.take();
+				*cur_link = next;
+			}
+		}
+	}
+}
+
+// Elements are appended at the end, so `extend` and `collect` keep the iteration order
+// (unlike `push`, which always inserts at the head).
+impl<T> Extend<T> for List<T> {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		let mut cur_link = self.tail_link();
+		for elem in iter {
+			let new_node = cur_link.insert(Box::new(Node {
+				elem,
+				next: None,
+			}));
+			cur_link = &mut new_node.next;
+		}
+	}
+}
+
+impl<T> FromIterator<T> for List<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		let mut list = List::new();
+		list.extend(iter);
+		list
+	}
+}
+
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.iter().eq(other.iter())
+	}
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: Clone> Clone for List<T> {
+	fn clone(&self) -> Self {
+		self.iter().cloned().collect()
+	}
+}
+
+// A persistent (immutable) list: `prepend` and `tail` never modify a list,
+// they return a new one which shares the remaining nodes through `Rc`.
+// like the `Cons/Nil` List in 02_custom_types.rs, but generic and cheap to clone
+mod persistent {
+	use std::rc::Rc;
+	
+	type Link<T> = Option<Rc<Node<T>>>;
+	
+	struct Node<T> {
+		elem: T,
+		next: Link<T>,
+	}
+	
+	pub struct List<T> {
+		head: Link<T>,
+	}
+	
+	impl<T> List<T> {
+		pub fn new() -> Self {
+			List { head: None }
+		}
+		
+		pub fn prepend(&self, elem: T) -> List<T> {
+			List {
+				head: Some(Rc::new(Node {
+					elem,
+					// Rc::clone only bumps the reference count, the nodes are shared
+					next: self.head.clone(),
+				})),
+			}
+		}
+		
+		pub fn tail(&self) -> List<T> {
+			// and_then: the tail of an empty list is an empty list
+			List { head: self.head.as_ref().and_then(|node| node.next.clone()) }
+		}
+		
+		pub fn head(&self) -> Option<&T> {
+			self.head.as_ref().map(|node| &node.elem)
+		}
+		
+		pub fn is_empty(&self) -> bool {
+			self.head.is_none()
+		}
+		
+		pub fn iter(&self) -> Iter<'_, T> {
+			Iter { next: self.head.as_deref() }
+		}
+	}
+	
+	// No `T: Clone` bound, cloning a list only clones the head `Rc`
+	impl<T> Clone for List<T> {
+		fn clone(&self) -> Self {
+			List { head: self.head.clone() }
+		}
+	}
+	
+	impl<T> Drop for List<T> {
+		fn drop(&mut self) {
+			let mut cur_link = self.head.take();
+			while let Some(node) = cur_link {
+				// Rc::try_unwrap only succeeds for the last owner of the node,
+				// if the node is still shared by another list we must stop here
+				if let Ok(mut node) = Rc::try_unwrap(node) {
+					cur_link = node.next.take();
+				} else {
+					break;
+				}
+			}
+		}
+	}
+	
+	pub struct Iter<'a, T> {
+		next: Option<&'a Node<T>>,
+	}
+	
+	impl<'a, T> Iterator for Iter<'a, T> {
+		type Item = &'a T;
+		
+		fn next(&mut self) -> Option<Self::Item> {
+			self.next.map(|node| {
+				self.next = node.next.as_deref();
+				&node.elem
+			})
+		}
+	}
+}
+
+// A doubly linked deque. Every node is owned by both of its neighbours,
+// so it lives in a Rc<RefCell<..>> (see 14_cell.rs) to be shared and still mutated.
+mod deque {
+	use std::cell::{Ref, RefCell, RefMut};
+	use std::rc::Rc;
+	
+	type Link<T> = Option<Rc<RefCell<Node<T>>>>;
+	
+	struct Node<T> {
+		elem: T,
+		next: Link<T>,
+		prev: Link<T>,
+	}
+	
+	impl<T> Node<T> {
+		fn new(elem: T) -> Rc<RefCell<Self>> {
+			Rc::new(RefCell::new(Node {
+				elem,
+				next: None,
+				prev: None,
+			}))
+		}
+		
+		// Only call it once the node is unlinked, the list must hold the last reference
+		fn into_elem(node: Rc<RefCell<Self>>) -> T {
+			// Rc::try_unwrap returns the Rc back on failure, which doesn't implement Debug,
+			// so `.unwrap()` can't be used directly
+			Rc::try_unwrap(node).ok().expect("node is still shared").into_inner().elem
+		}
+	}
+	
+	pub struct Deque<T> {
+		head: Link<T>,
+		tail: Link<T>,
+		len: usize,
+	}
+	
+	impl<T> Deque<T> {
+		pub fn new() -> Self {
+			Deque { head: None, tail: None, len: 0 }
+		}
+		
+		pub fn len(&self) -> usize {
+			self.len
+		}
+		
+		pub fn is_empty(&self) -> bool {
+			self.len == 0
+		}
+		
+		pub fn push_front(&mut self, elem: T) {
+			let new_head = Node::new(elem);
+			match self.head.take() {
+				Some(old_head) => {
+					old_head.borrow_mut().prev = Some(new_head.clone());
+					new_head.borrow_mut().next = Some(old_head);
+					self.head = Some(new_head);
+				}
+				None => {
+					self.tail = Some(new_head.clone());
+					self.head = Some(new_head);
+				}
+			}
+			self.len += 1;
+		}
+		
+		pub fn push_back(&mut self, elem: T) {
+			let new_tail = Node::new(elem);
+			match self.tail.take() {
+				Some(old_tail) => {
+					old_tail.borrow_mut().next = Some(new_tail.clone());
+					new_tail.borrow_mut().prev = Some(old_tail);
+					self.tail = Some(new_tail);
+				}
+				None => {
+					self.head = Some(new_tail.clone());
+					self.tail = Some(new_tail);
+				}
+			}
+			self.len += 1;
+		}
+		
+		pub fn pop_front(&mut self) -> Option<T> {
+			self.head.take().map(|old_head| {
+				match old_head.borrow_mut().next.take() {
+					Some(new_head) => {
+						new_head.borrow_mut().prev.take();
+						self.head = Some(new_head);
+					}
+					None => {
+						self.tail.take();
+					}
+				}
+				self.len -= 1;
+				Node::into_elem(old_head)
+			})
+		}
+		
+		pub fn pop_back(&mut self) -> Option<T> {
+			self.tail.take().map(|old_tail| {
+				match old_tail.borrow_mut().prev.take() {
+					Some(new_tail) => {
+						new_tail.borrow_mut().next.take();
+						self.tail = Some(new_tail);
+					}
+					None => {
+						self.head.take();
+					}
+				}
+				self.len -= 1;
+				Node::into_elem(old_tail)
+			})
+		}
+		
+		// error[E0515]: cannot return value referencing temporary value
+		//self.head.as_ref().map(|node| &node.borrow().elem)
+		// Ref::map keeps the RefCell borrowed while the returned Ref is alive
+		pub fn peek_front(&self) -> Option<Ref<'_, T>> {
+			self.head.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.elem))
+		}
+		
+		pub fn peek_back(&self) -> Option<Ref<'_, T>> {
+			self.tail.as_ref().map(|node| Ref::map(node.borrow(), |node| &node.elem))
+		}
+		
+		pub fn peek_front_mut(&mut self) -> Option<RefMut<'_, T>> {
+			self.head.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+		}
+		
+		pub fn peek_back_mut(&mut self) -> Option<RefMut<'_, T>> {
+			self.tail.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+		}
+		
+		pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+			let cur = self.head.clone();
+			let index = cur.as_ref().map(|_| 0);
+			CursorMut { list: self, cur, index }
+		}
+		
+		pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+			let cur = self.tail.clone();
+			let index = cur.as_ref().map(|_| self.len - 1);
+			CursorMut { list: self, cur, index }
+		}
+	}
+	
+	// head <-> tail 互相引用形成了循环, 必须手动断开, 否则结点永远不会被释放
+	impl<T> Drop for Deque<T> {
+		fn drop(&mut self) {
+			while self.pop_front().is_some() {}
+		}
+	}
+	
+	pub struct IntoIter<T>(Deque<T>);
+	
+	impl<T> IntoIterator for Deque<T> {
+		type Item = T;
+		type IntoIter = IntoIter<T>;
+		
+		fn into_iter(self) -> Self::IntoIter {
+			IntoIter(self)
+		}
+	}
+	
+	impl<T> Iterator for IntoIter<T> {
+		type Item = T;
+		
+		fn next(&mut self) -> Option<Self::Item> {
+			self.0.pop_front()
+		}
+		
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			(self.0.len, Some(self.0.len))
+		}
+	}
+	
+	impl<T> DoubleEndedIterator for IntoIter<T> {
+		fn next_back(&mut self) -> Option<Self::Item> {
+			self.0.pop_back()
+		}
+	}
+	
+	// A cursor points either at an element or at the "ghost" position
+	// between the tail and the head (cur == None), the same model as
+	// std::collections::linked_list::CursorMut.
+	pub struct CursorMut<'a, T> {
+		list: &'a mut Deque<T>,
+		cur: Link<T>,
+		index: Option<usize>,
+	}
+	
+	impl<'a, T> CursorMut<'a, T> {
+		pub fn index(&self) -> Option<usize> {
+			self.index
+		}
+		
+		pub fn current(&mut self) -> Option<RefMut<'_, T>> {
+			self.cur.as_ref().map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+		}
+		
+		// Moving past the tail lands on the ghost, moving from the ghost goes to the head
+		pub fn move_next(&mut self) {
+			match self.cur.take() {
+				Some(cur) => {
+					self.cur = cur.borrow().next.clone();
+					self.index = match self.cur {
+						Some(_) => self.index.map(|i| i + 1),
+						None => None,
+					};
+				}
+				None => {
+					self.cur = self.list.head.clone();
+					self.index = self.cur.as_ref().map(|_| 0);
+				}
+			}
+		}
+		
+		pub fn move_prev(&mut self) {
+			match self.cur.take() {
+				Some(cur) => {
+					self.cur = cur.borrow().prev.clone();
+					self.index = match self.cur {
+						Some(_) => self.index.map(|i| i - 1),
+						None => None,
+					};
+				}
+				None => {
+					self.cur = self.list.tail.clone();
+					self.index = self.cur.as_ref().map(|_| self.list.len - 1);
+				}
+			}
+		}
+		
+		// On the ghost, inserting after means inserting at the front
+		pub fn insert_after(&mut self, elem: T) {
+			let cur = match self.cur {
+				Some(ref cur) => cur,
+				None => return self.list.push_front(elem),
+			};
+			
+			let new_node = Node::new(elem);
+			match cur.borrow_mut().next.take() {
+				Some(next) => {
+					next.borrow_mut().prev = Some(new_node.clone());
+					new_node.borrow_mut().next = Some(next);
+				}
+				None => self.list.tail = Some(new_node.clone()),
+			}
+			new_node.borrow_mut().prev = Some(cur.clone());
+			cur.borrow_mut().next = Some(new_node);
+			self.list.len += 1;
+		}
+		
+		// On the ghost, inserting before means inserting at the back
+		pub fn insert_before(&mut self, elem: T) {
+			let cur = match self.cur {
+				Some(ref cur) => cur,
+				None => return self.list.push_back(elem),
+			};
+			
+			let new_node = Node::new(elem);
+			match cur.borrow_mut().prev.take() {
+				Some(prev) => {
+					prev.borrow_mut().next = Some(new_node.clone());
+					new_node.borrow_mut().prev = Some(prev);
+				}
+				None => self.list.head = Some(new_node.clone()),
+			}
+			new_node.borrow_mut().next = Some(cur.clone());
+			cur.borrow_mut().prev = Some(new_node);
+			self.list.len += 1;
+			self.index = self.index.map(|i| i + 1);
+		}
+		
+		// Removes the current element and moves the cursor to the next one
+		pub fn remove_current(&mut self) -> Option<T> {
+			let cur = self.cur.take()?;
+			let prev = cur.borrow_mut().prev.take();
+			let next = cur.borrow_mut().next.take();
+			
+			match prev {
+				Some(ref prev) => prev.borrow_mut().next = next.clone(),
+				None => self.list.head = next.clone(),
+			}
+			match next {
+				Some(ref next) => next.borrow_mut().prev = prev,
+				None => self.list.tail = prev,
+			}
+			
+			if next.is_none() {
+				self.index = None;
+			}
+			self.cur = next;
+			self.list.len -= 1;
+			
+			Some(Node::into_elem(cur))
+		}
+	}
+}
+
+// A singly linked queue keeping a pointer to its last node, so both
+// push_back and pop_front are O(1). The tail is aliased by the `next` of
+// the node before it, which safe references can't express, so every link
+// is a raw NonNull pointer (like `Unmovable::slice` in 16_pin.rs).
+// Mixing Box links with a raw tail pointer breaks Miri's stacked borrows:
+// the Box would claim unique ownership of a node the tail still points to.
+mod queue {
+	use std::marker::PhantomData;
+	use std::ptr::NonNull;
+	
+	type Link<T> = Option<NonNull<Node<T>>>;
+	
+	struct Node<T> {
+		elem: T,
+		next: Link<T>,
+	}
+	
+	pub struct Queue<T> {
+		head: Link<T>,
+		tail: Link<T>,
+		// tells the compiler (drop check, variance) that we own the nodes
+		_boo: PhantomData<Box<Node<T>>>,
+	}
+	
+	impl<T> Queue<T> {
+		pub fn new() -> Self {
+			Queue { head: None, tail: None, _boo: PhantomData }
+		}
+		
+		pub fn is_empty(&self) -> bool {
+			self.head.is_none()
+		}
+		
+		pub fn push_back(&mut self, elem: T) {
+			// Box::into_raw never returns null
+			let new_tail = unsafe {
+				NonNull::new_unchecked(Box::into_raw(Box::new(Node {
+					elem,
+					next: None,
+				})))
+			};
+			
+			match self.tail {
+				// the old tail is a node owned by this queue, nobody else borrows it here
+				Some(old_tail) => unsafe { (*old_tail.as_ptr()).next = Some(new_tail) },
+				None => self.head = Some(new_tail),
+			}
+			self.tail = Some(new_tail);
+		}
+		
+		pub fn pop_front(&mut self) -> Option<T> {
+			self.head.map(|head| {
+				// take the ownership back, the node is freed at the end of this closure
+				let boxed_node = unsafe { Box::from_raw(head.as_ptr()) };
+				self.head = boxed_node.next;
+				if self.head.is_none() {
+					self.tail = None;
+				}
+				boxed_node.elem
+			})
+		}
+		
+		pub fn peek_front(&self) -> Option<&T> {
+			self.head.map(|node| unsafe { &(*node.as_ptr()).elem })
+		}
+		
+		pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+			self.head.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+		}
+		
+		pub fn peek_back(&self) -> Option<&T> {
+			self.tail.map(|node| unsafe { &(*node.as_ptr()).elem })
+		}
+		
+		pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+			self.tail.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+		}
+		
+		pub fn iter(&self) -> Iter<'_, T> {
+			Iter { next: self.head, _boo: PhantomData }
+		}
+		
+		pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+			IterMut { next: self.head, _boo: PhantomData }
+		}
+	}
+	
+	impl<T> Drop for Queue<T> {
+		fn drop(&mut self) {
+			while self.pop_front().is_some() {}
+		}
+	}
+	
+	pub struct IntoIter<T>(Queue<T>);
+	
+	impl<T> IntoIterator for Queue<T> {
+		type Item = T;
+		type IntoIter = IntoIter<T>;
+		
+		fn into_iter(self) -> Self::IntoIter {
+			IntoIter(self)
+		}
+	}
+	
+	impl<T> Iterator for IntoIter<T> {
+		type Item = T;
+		
+		fn next(&mut self) -> Option<Self::Item> {
+			self.0.pop_front()
+		}
+	}
+	
+	// The lifetime 'a only lives in the PhantomData, it ties the returned
+	// references to the borrow of the queue taken by iter()/iter_mut()
+	pub struct Iter<'a, T> {
+		next: Link<T>,
+		_boo: PhantomData<&'a T>,
+	}
+	
+	impl<'a, T> Iterator for Iter<'a, T> {
+		type Item = &'a T;
+		
+		fn next(&mut self) -> Option<Self::Item> {
+			self.next.map(|node| unsafe {
+				let node = &*node.as_ptr();
+				self.next = node.next;
+				&node.elem
+			})
+		}
+	}
+	
+	pub struct IterMut<'a, T> {
+		next: Link<T>,
+		_boo: PhantomData<&'a mut T>,
+	}
+	
+	impl<'a, T> Iterator for IterMut<'a, T> {
+		type Item = &'a mut T;
+		
+		fn next(&mut self) -> Option<Self::Item> {
+			// every node is handed out at most once, so the &mut never alias
+			self.next.map(|node| unsafe {
+				let node = &mut *node.as_ptr();
+				self.next = node.next;
+				&mut node.elem
+			})
+		}
+	}
+}
+
 fn test_basics() {
 	let mut list = List::new();
 	
@@ -208,9 +914,455 @@ fn test_iter() {
 	assert_eq!(iter.next(), None);
 }
 
+fn test_iter_mut() {
+	let mut list = List::new();
+	list.push(1);
+	list.push(2);
+	list.push(3);
+	
+	let mut iter = list.iter_mut();
+	assert_eq!(iter.next(), Some(&mut 3));
+	assert_eq!(iter.next(), Some(&mut 2));
+	assert_eq!(iter.next(), Some(&mut 1));
+	assert_eq!(iter.next(), None);
+	
+	for v in &mut list {
+		*v *= 10;
+	}
+	assert_eq!(list.iter().collect::<Vec<_>>(), vec![&30, &20, &10]);
+}
+
+fn test_from_iter_extend() {
+	let mut list: List<i32> = vec![1, 2, 3].into_iter().collect();
+	assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+	
+	list.extend(vec![4, 5]);
+	assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+	
+	let mut empty = List::new();
+	empty.extend(0..2);
+	assert_eq!(empty.pop(), Some(0));
+	assert_eq!(empty.pop(), Some(1));
+	assert_eq!(empty.pop(), None);
+}
+
+fn test_len_reverse() {
+	let mut list: List<i32> = List::new();
+	assert!(list.is_empty());
+	assert_eq!(list.len(), 0);
+	list.reverse();
+	assert!(list.is_empty());
+	
+	let mut list: List<i32> = (1..=4).collect();
+	assert!(!list.is_empty());
+	assert_eq!(list.len(), 4);
+	
+	list.reverse();
+	assert_eq!(list.len(), 4);
+	assert_eq!(list.iter().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+}
+
+fn test_append_split_off() {
+	let mut list: List<i32> = (1..=3).collect();
+	let mut other: List<i32> = (4..=6).collect();
+	
+	list.append(&mut other);
+	assert!(other.is_empty());
+	assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6]);
+	
+	let tail = list.split_off(4);
+	assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+	assert_eq!(tail.iter().collect::<Vec<_>>(), vec![&5, &6]);
+	
+	let all = list.split_off(0);
+	assert!(list.is_empty());
+	assert_eq!(all.len(), 4);
+	
+	let mut all = all;
+	let none = all.split_off(4);
+	assert!(none.is_empty());
+	assert_eq!(all.len(), 4);
+	
+	// appending to an empty list just moves the nodes over
+	list.append(&mut all);
+	assert_eq!(list.len(), 4);
+	assert!(all.is_empty());
+}
+
+fn test_retain() {
+	let mut list: List<i32> = (1..=10).collect();
+	list.retain(|v| v % 2 == 0);
+	assert_eq!(list.iter().collect::<Vec<_>>(), vec![&2, &4, &6, &8, &10]);
+	
+	list.retain(|v| *v > 100);
+	assert!(list.is_empty());
+}
+
+fn test_debug_eq_clone() {
+	let mut list: List<&str> = List::new();
+	assert_eq!(format!("{:?}", list), "[]");
+	
+	list.push("who");
+	list.push("am");
+	list.push("i");
+	assert_eq!(format!("{:?}", list), r#"["i", "am", "who"]"#);
+	
+	let cloned = list.clone();
+	assert_eq!(list, cloned);
+	assert_eq!(format!("{:?}", cloned), r#"["i", "am", "who"]"#);
+	
+	list.pop();
+	assert_ne!(list, cloned);
+	assert_eq!(cloned.len(), 3);
+}
+
+fn test_long_list_drop() {
+	// drop, reverse and retain don't recurse, so a long list can't overflow the stack
+	let mut list: List<u32> = (0..100_000).collect();
+	list.reverse();
+	list.retain(|v| v % 3 != 0);
+	assert_eq!(list.peek(), Some(&99_998));
+}
+
+fn test_persistent_basics() {
+	let list = persistent::List::new();
+	assert_eq!(list.head(), None);
+	assert!(list.is_empty());
+	
+	let list = list.prepend(1).prepend(2).prepend(3);
+	assert_eq!(list.head(), Some(&3));
+	
+	let list = list.tail();
+	assert_eq!(list.head(), Some(&2));
+	
+	let list = list.tail();
+	assert_eq!(list.head(), Some(&1));
+	
+	let list = list.tail();
+	assert_eq!(list.head(), None);
+	
+	// tail of an empty list is still an empty list
+	let list = list.tail();
+	assert!(list.is_empty());
+}
+
+fn test_persistent_iter() {
+	let list = persistent::List::new().prepend(1).prepend(2).prepend(3);
+	
+	let mut iter = list.iter();
+	assert_eq!(iter.next(), Some(&3));
+	assert_eq!(iter.next(), Some(&2));
+	assert_eq!(iter.next(), Some(&1));
+	assert_eq!(iter.next(), None);
+}
+
+fn test_persistent_sharing() {
+	let base = persistent::List::new().prepend(1).prepend(2);
+	// both lists share the nodes of `base`
+	let a = base.prepend(10);
+	let b = base.prepend(20);
+	let c = a.clone();
+	
+	assert_eq!(a.iter().collect::<Vec<_>>(), vec![&10, &2, &1]);
+	assert_eq!(b.iter().collect::<Vec<_>>(), vec![&20, &2, &1]);
+	assert!(std::ptr::eq(a.tail().head().unwrap(), b.tail().head().unwrap()));
+	
+	// dropping `base` and `a` must not free the nodes still reachable from `b` and `c`
+	drop(base);
+	drop(a);
+	assert_eq!(b.iter().collect::<Vec<_>>(), vec![&20, &2, &1]);
+	assert_eq!(c.iter().collect::<Vec<_>>(), vec![&10, &2, &1]);
+}
+
+fn test_persistent_long_list_drop() {
+	let mut list = persistent::List::new();
+	for i in 0..100_000 {
+		list = list.prepend(i);
+	}
+	let shared = list.tail().tail();
+	
+	// the Drop of `list` stops at the first node still owned by `shared`
+	drop(list);
+	assert_eq!(shared.head(), Some(&99_997));
+	drop(shared);
+}
+
+fn test_deque_basics() {
+	let mut deque = deque::Deque::new();
+	assert_eq!(deque.pop_front(), None);
+	assert_eq!(deque.pop_back(), None);
+	
+	deque.push_front(1);
+	deque.push_front(2);
+	deque.push_back(3);
+	deque.push_back(4);
+	assert_eq!(deque.len(), 4);
+	
+	// 2 1 3 4
+	assert_eq!(deque.pop_front(), Some(2));
+	assert_eq!(deque.pop_back(), Some(4));
+	assert_eq!(deque.pop_back(), Some(3));
+	assert_eq!(deque.pop_back(), Some(1));
+	assert_eq!(deque.pop_back(), None);
+	assert!(deque.is_empty());
+	
+	deque.push_back(5);
+	assert_eq!(deque.pop_front(), Some(5));
+	assert_eq!(deque.pop_front(), None);
+}
+
+fn test_deque_peek() {
+	let mut deque = deque::Deque::new();
+	assert!(deque.peek_front().is_none());
+	assert!(deque.peek_back().is_none());
+	
+	deque.push_front(1);
+	deque.push_front(2);
+	deque.push_front(3);
+	
+	assert_eq!(&*deque.peek_front().unwrap(), &3);
+	assert_eq!(&*deque.peek_back().unwrap(), &1);
+	
+	*deque.peek_front_mut().unwrap() = 30;
+	*deque.peek_back_mut().unwrap() *= 10;
+	assert_eq!(deque.pop_front(), Some(30));
+	assert_eq!(deque.pop_back(), Some(10));
+}
+
+fn test_deque_into_iter() {
+	let mut deque = deque::Deque::new();
+	deque.push_back(1);
+	deque.push_back(2);
+	deque.push_back(3);
+	deque.push_back(4);
+	
+	let mut iter = deque.into_iter();
+	assert_eq!(iter.size_hint(), (4, Some(4)));
+	assert_eq!(iter.next(), Some(1));
+	assert_eq!(iter.next_back(), Some(4));
+	assert_eq!(iter.next(), Some(2));
+	assert_eq!(iter.next_back(), Some(3));
+	assert_eq!(iter.next(), None);
+	assert_eq!(iter.next_back(), None);
+}
+
+fn test_deque_cursor() {
+	let mut deque = deque::Deque::new();
+	for &i in [1, 2, 4, 5].iter() {
+		deque.push_back(i);
+	}
+	
+	{
+		let mut cursor = deque.cursor_front_mut();
+		assert_eq!(cursor.index(), Some(0));
+		cursor.move_next();
+		assert_eq!(*cursor.current().unwrap(), 2);
+		
+		// 1 2 3 4 5
+		cursor.insert_after(3);
+		cursor.move_next();
+		assert_eq!(cursor.index(), Some(2));
+		*cursor.current().unwrap() *= 10;
+		
+		// remove 30, the cursor moves on to 4
+		assert_eq!(cursor.remove_current(), Some(30));
+		assert_eq!(*cursor.current().unwrap(), 4);
+		assert_eq!(cursor.index(), Some(2));
+		
+		cursor.insert_before(3);
+		assert_eq!(cursor.index(), Some(3));
+		
+		// walk past the tail onto the ghost, then wrap around to the head
+		cursor.move_next();
+		cursor.move_next();
+		assert!(cursor.current().is_none());
+		assert_eq!(cursor.index(), None);
+		cursor.insert_after(0);
+		cursor.insert_before(6);
+		cursor.move_next();
+		assert_eq!(*cursor.current().unwrap(), 0);
+		assert_eq!(cursor.index(), Some(0));
+		
+		// removing the head updates the deque
+		assert_eq!(cursor.remove_current(), Some(0));
+	}
+	
+	assert_eq!(deque.len(), 6);
+	assert_eq!(&*deque.peek_front().unwrap(), &1);
+	
+	{
+		let mut cursor = deque.cursor_back_mut();
+		assert_eq!(cursor.index(), Some(5));
+		// removing the tail moves the cursor onto the ghost
+		assert_eq!(cursor.remove_current(), Some(6));
+		assert_eq!(cursor.index(), None);
+		cursor.move_prev();
+		assert_eq!(*cursor.current().unwrap(), 5);
+		assert_eq!(cursor.index(), Some(4));
+	}
+	
+	assert_eq!(&*deque.peek_back().unwrap(), &5);
+	assert_eq!(deque.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+}
+
+fn test_deque_drop() {
+	use std::rc::Rc;
+	
+	// every element must be freed, even though the nodes reference each other
+	let tracker = Rc::new(());
+	let mut deque = deque::Deque::new();
+	for _ in 0..100_000 {
+		deque.push_back(tracker.clone());
+	}
+	assert_eq!(Rc::strong_count(&tracker), 100_001);
+	drop(deque);
+	assert_eq!(Rc::strong_count(&tracker), 1);
+}
+
+fn test_queue_basics() {
+	let mut queue = queue::Queue::new();
+	assert_eq!(queue.pop_front(), None);
+	assert!(queue.is_empty());
+	
+	queue.push_back(1);
+	queue.push_back(2);
+	queue.push_back(3);
+	
+	assert_eq!(queue.pop_front(), Some(1));
+	assert_eq!(queue.pop_front(), Some(2));
+	
+	queue.push_back(4);
+	queue.push_back(5);
+	
+	assert_eq!(queue.pop_front(), Some(3));
+	assert_eq!(queue.pop_front(), Some(4));
+	
+	// exhaustion resets the tail
+	assert_eq!(queue.pop_front(), Some(5));
+	assert_eq!(queue.pop_front(), None);
+	
+	queue.push_back(6);
+	queue.push_back(7);
+	assert_eq!(queue.pop_front(), Some(6));
+	assert_eq!(queue.pop_front(), Some(7));
+	assert_eq!(queue.pop_front(), None);
+}
+
+fn test_queue_peek() {
+	let mut queue = queue::Queue::new();
+	assert_eq!(queue.peek_front(), None);
+	assert_eq!(queue.peek_back(), None);
+	
+	queue.push_back(1);
+	queue.push_back(2);
+	queue.push_back(3);
+	assert_eq!(queue.peek_front(), Some(&1));
+	assert_eq!(queue.peek_back(), Some(&3));
+	
+	if let Some(v) = queue.peek_front_mut() {
+		*v *= 10;
+	}
+	if let Some(v) = queue.peek_back_mut() {
+		*v *= 10;
+	}
+	assert_eq!(queue.pop_front(), Some(10));
+	assert_eq!(queue.pop_front(), Some(2));
+	assert_eq!(queue.pop_front(), Some(30));
+}
+
+fn test_queue_iters() {
+	let mut queue = queue::Queue::new();
+	queue.push_back(1);
+	queue.push_back(2);
+	queue.push_back(3);
+	
+	let mut iter = queue.iter();
+	assert_eq!(iter.next(), Some(&1));
+	assert_eq!(iter.next(), Some(&2));
+	assert_eq!(iter.next(), Some(&3));
+	assert_eq!(iter.next(), None);
+	
+	let mut iter = queue.iter_mut();
+	assert_eq!(iter.next(), Some(&mut 1));
+	assert_eq!(iter.next(), Some(&mut 2));
+	assert_eq!(iter.next(), Some(&mut 3));
+	assert_eq!(iter.next(), None);
+	
+	let mut iter = queue.into_iter();
+	assert_eq!(iter.next(), Some(1));
+	assert_eq!(iter.next(), Some(2));
+	assert_eq!(iter.next(), Some(3));
+	assert_eq!(iter.next(), None);
+}
+
+// Interleaves raw-pointer writes with outstanding references,
+// run with `cargo miri run` to check it against stacked borrows
+fn test_queue_miri_food() {
+	let mut queue = queue::Queue::new();
+	
+	queue.push_back(1);
+	queue.push_back(2);
+	queue.push_back(3);
+	
+	assert!(queue.pop_front() == Some(1));
+	queue.push_back(4);
+	assert!(queue.pop_front() == Some(2));
+	queue.push_back(5);
+	
+	assert!(queue.peek_front() == Some(&3));
+	queue.push_back(6);
+	if let Some(x) = queue.peek_back_mut() {
+		*x *= 10;
+	}
+	assert!(queue.peek_back() == Some(&60));
+	
+	for elem in queue.iter_mut() {
+		*elem *= 100;
+	}
+	
+	let mut iter = queue.iter();
+	assert_eq!(iter.next(), Some(&300));
+	assert_eq!(iter.next(), Some(&400));
+	assert_eq!(iter.next(), Some(&500));
+	assert_eq!(iter.next(), Some(&6000));
+	assert_eq!(iter.next(), None);
+	
+	assert!(queue.pop_front() == Some(300));
+	if let Some(x) = queue.peek_front_mut() {
+		*x *= 10;
+	}
+	assert!(queue.peek_front() == Some(&4000));
+	queue.push_back(7);
+	
+	// drop it on the ground and let Drop free the remaining nodes
+}
+
 fn main() {
 	test_basics();
 	test_peek();
 	test_into_iter();
 	test_iter();
+	test_iter_mut();
+	test_from_iter_extend();
+	test_len_reverse();
+	test_append_split_off();
+	test_retain();
+	test_debug_eq_clone();
+	test_long_list_drop();
+	
+	test_persistent_basics();
+	test_persistent_iter();
+	test_persistent_sharing();
+	test_persistent_long_list_drop();
+	
+	test_deque_basics();
+	test_deque_peek();
+	test_deque_into_iter();
+	test_deque_cursor();
+	test_deque_drop();
+	
+	test_queue_basics();
+	test_queue_peek();
+	test_queue_iters();
+	test_queue_miri_food();
 }
\ No newline at end of file
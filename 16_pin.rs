@@ -109,6 +109,369 @@ fn pin4() {
 }
 
 
+// A generic version of `Unmovable`: the pinned owner plus a view borrowed from it,
+// e.g. SelfRef<String, str> keeps a `&str` slice of its own `String`.
+// B: ?Sized so that the view can be a `str` or a `[T]` slice
+struct SelfRef<O, B: ?Sized> {
+	owner: O,
+	// None only until `new` has set it, `NonNull::dangling()` doesn't exist for unsized types
+	view: Option<NonNull<B>>,
+	_pin: PhantomPinned,
+}
+
+impl<O, B: ?Sized> SelfRef<O, B> {
+	// `make_view` must work for any lifetime 'a (higher-ranked trait bound),
+	// so the closure can't smuggle a reference to something else than the owner
+	fn new<F>(owner: O, make_view: F) -> Pin<Box<Self>>
+		where F: for<'a> FnOnce(&'a O) -> &'a B {
+		
+		let mut boxed = Box::pin(SelfRef {
+			owner,
+			view: None,
+			_pin: PhantomPinned,
+		});
+		
+		// the owner is already at its final address inside the pinned box
+		let view = NonNull::from(make_view(&boxed.owner));
+		
+		unsafe {
+			let mut_ref: Pin<&mut Self> = boxed.as_mut();
+			Pin::get_unchecked_mut(mut_ref).view = Some(view);
+		}
+		
+		boxed
+	}
+	
+	// Only a shared reference is handed out: a `&mut O` would allow changing
+	// (and reallocating) the owner under the view
+	fn owner(&self) -> &O {
+		&self.owner
+	}
+	
+	// The view can't outlive `self`, so it can't outlive the owner it points into
+	fn view(&self) -> &B {
+		let view = self.view.expect("view is set by SelfRef::new");
+		// the owner is pinned and never mutated, the pointer is as valid as `&self.owner`
+		unsafe { view.as_ref() }
+	}
+}
+
+fn pin5() {
+	println!("~pin5~");
+	
+	let text = SelfRef::new(String::from("hello pinned world"), |s: &String| {
+		s.split_whitespace().nth(1).unwrap()
+	});
+	let view_addr = text.view().as_ptr();
+	
+	// moving the Pin<Box<_>> only moves the pointer, not the pinned value
+	let moved_text = text;
+	let mut texts = Vec::new();
+	texts.push(moved_text);
+	let still_text = texts.pop().unwrap();
+	
+	assert_eq!(still_text.view(), "pinned");
+	assert_eq!(still_text.view().as_ptr(), view_addr);
+	assert_eq!(still_text.view().as_ptr(), still_text.owner()[6..].as_ptr());
+	println!("owner: {}, view: {}", still_text.owner(), still_text.view());
+	
+	// the view may point into the owner itself (not just into a heap buffer
+	// the owner manages), that is what the pinning is needed for
+	let bytes = SelfRef::new([1u8, 2, 3, 4, 5, 6, 7, 8], |a: &[u8; 8]| &a[2..5]);
+	let owner_addr = bytes.owner() as *const [u8; 8];
+	let bytes = move_across_function(bytes);
+	assert_eq!(bytes.view(), &[3, 4, 5]);
+	assert_eq!(bytes.owner() as *const [u8; 8], owner_addr);
+	assert_eq!(bytes.view().as_ptr(), bytes.owner()[2..].as_ptr());
+	
+	// a sized view works as well
+	let max = SelfRef::new(vec![3, 9, 4], |v: &Vec<i32>| v.iter().max().unwrap());
+	assert_eq!(*max.view(), 9);
+	assert!(std::ptr::eq(max.view(), &max.owner()[1]));
+	
+	// error[E0277]: `PhantomPinned` cannot be unpinned
+	//let mut other = SelfRef::new([0u8; 8], |a: &[u8; 8]| &a[..]);
+	//mem::swap(&mut *bytes, &mut *other);
+}
+
+// passing by value and returning moves `value` twice
+fn move_across_function<T>(value: T) -> T {
+	value
+}
+
+// An intrusive doubly linked list: the list doesn't allocate, the nodes live in
+// memory owned by the callers (on their stack, in their futures, ...) and are
+// linked by raw pointers. That is only sound because the nodes are pinned:
+// a pinned value never moves, and its memory is not reused before its `Drop`
+// ran, so a node can always unlink itself before it goes away.
+mod intrusive {
+	use std::cell::Cell;
+	use std::marker::PhantomPinned;
+	use std::pin::Pin;
+	use std::process;
+	use std::ptr::NonNull;
+	
+	type Link<T> = Cell<Option<NonNull<Node<T>>>>;
+	
+	// The links are only ever accessed through shared references (the owner of
+	// a node may hold one at any time), so they are Cells, like in 14_cell.rs
+	pub struct Node<T> {
+		value: T,
+		prev: Link<T>,
+		next: Link<T>,
+		// the list this node is linked into, None while unlinked
+		list: Cell<Option<NonNull<List<T>>>>,
+		// number of `&value` the list currently lends out to callbacks
+		lent: Cell<usize>,
+		_pin: PhantomPinned,
+	}
+	
+	impl<T> Node<T> {
+		pub fn new(value: T) -> Self {
+			Node {
+				value,
+				prev: Cell::new(None),
+				next: Cell::new(None),
+				list: Cell::new(None),
+				lent: Cell::new(0),
+				_pin: PhantomPinned,
+			}
+		}
+		
+		pub fn value(&self) -> &T {
+			&self.value
+		}
+		
+		pub fn is_linked(&self) -> bool {
+			self.list.get().is_some()
+		}
+		
+		// Removes the node from its list, does nothing if it isn't linked
+		pub fn unlink(&self) {
+			let list = match self.list.take() {
+				Some(list) => unsafe { list.as_ref() },
+				None => return,
+			};
+			let prev = self.prev.take();
+			let next = self.next.take();
+			
+			// while linked, the neighbours and the list are pinned and alive,
+			// otherwise their Drop would already have unlinked this node
+			match prev {
+				Some(prev) => unsafe { prev.as_ref() }.next.set(next),
+				None => list.head.set(next),
+			}
+			match next {
+				Some(next) => unsafe { next.as_ref() }.prev.set(prev),
+				None => list.tail.set(prev),
+			}
+			list.len.set(list.len.get() - 1);
+		}
+	}
+	
+	impl<T> Drop for Node<T> {
+		fn drop(&mut self) {
+			if self.lent.get() > 0 {
+				// A callback dropped the very node whose value it is looking at (e.g. through
+				// an Option<Pin<Box<Node>>> it captured). Its `&T` would dangle and there is
+				// no way to keep the memory alive. A panic could be caught and the callback
+				// would go on with the dangling `&T`, so abort like a double panic does.
+				process::abort();
+			}
+			self.unlink();
+		}
+	}
+	
+	pub struct List<T> {
+		head: Link<T>,
+		tail: Link<T>,
+		len: Cell<usize>,
+		// linked nodes point back to the list, so it must not move either
+		_pin: PhantomPinned,
+	}
+	
+	impl<T> List<T> {
+		pub fn new() -> Self {
+			List {
+				head: Cell::new(None),
+				tail: Cell::new(None),
+				len: Cell::new(0),
+				_pin: PhantomPinned,
+			}
+		}
+		
+		pub fn len(&self) -> usize {
+			self.len.get()
+		}
+		
+		pub fn is_empty(&self) -> bool {
+			self.len.get() == 0
+		}
+		
+		// Panics if the node is already linked into a list
+		pub fn push_back(self: Pin<&Self>, node: Pin<&mut Node<T>>) {
+			let node = self.link(node);
+			let node_ptr = NonNull::from(node);
+			
+			node.prev.set(self.tail.get());
+			match self.tail.get() {
+				Some(tail) => unsafe { tail.as_ref() }.next.set(Some(node_ptr)),
+				None => self.head.set(Some(node_ptr)),
+			}
+			self.tail.set(Some(node_ptr));
+		}
+		
+		// Panics if the node is already linked into a list
+		pub fn push_front(self: Pin<&Self>, node: Pin<&mut Node<T>>) {
+			let node = self.link(node);
+			let node_ptr = NonNull::from(node);
+			
+			node.next.set(self.head.get());
+			match self.head.get() {
+				Some(head) => unsafe { head.as_ref() }.prev.set(Some(node_ptr)),
+				None => self.tail.set(Some(node_ptr)),
+			}
+			self.head.set(Some(node_ptr));
+		}
+		
+		fn link<'a>(self: Pin<&Self>, node: Pin<&'a mut Node<T>>) -> &'a Node<T> {
+			// from here on the node is only used through shared references
+			let node = node.into_ref().get_ref();
+			assert!(!node.is_linked(), "node is already linked into a list");
+			
+			node.list.set(Some(NonNull::from(self.get_ref())));
+			self.len.set(self.len.get() + 1);
+			node
+		}
+		
+		// The list can't hand out a plain `&T`: nothing stops the owner from dropping
+		// the node while that reference is alive. The value is only lent to `f` instead.
+		pub fn pop_front_with<R, F>(self: Pin<&Self>, f: F) -> Option<R>
+			where F: FnOnce(&T) -> R {
+			
+			let node = unsafe { self.head.get()?.as_ref() };
+			node.unlink();
+			Some(lend(node, f))
+		}
+		
+		pub fn pop_back_with<R, F>(self: Pin<&Self>, f: F) -> Option<R>
+			where F: FnOnce(&T) -> R {
+			
+			let node = unsafe { self.tail.get()?.as_ref() };
+			node.unlink();
+			Some(lend(node, f))
+		}
+		
+		// `f` may unlink or drop other nodes, the next node is only looked up
+		// once it returned. If it unlinks the current node the iteration stops.
+		pub fn for_each<F>(self: Pin<&Self>, mut f: F)
+			where F: FnMut(&T) {
+			
+			let mut cur = self.head.get();
+			while let Some(node) = cur {
+				let node = unsafe { node.as_ref() };
+				lend(node, &mut f);
+				cur = node.next.get();
+			}
+		}
+	}
+	
+	// The list goes away first: detach the nodes so they don't point to freed memory
+	impl<T> Drop for List<T> {
+		fn drop(&mut self) {
+			let mut cur = self.head.take();
+			while let Some(node) = cur {
+				let node = unsafe { node.as_ref() };
+				cur = node.next.take();
+				node.prev.set(None);
+				node.list.set(None);
+			}
+			self.tail.set(None);
+			self.len.set(0);
+		}
+	}
+	
+	fn lend<T, R, F>(node: &Node<T>, f: F) -> R
+		where F: FnOnce(&T) -> R {
+		
+		// restores the counter even if `f` panics
+		struct Lent<'a>(&'a Cell<usize>);
+		
+		impl Drop for Lent<'_> {
+			fn drop(&mut self) {
+				self.0.set(self.0.get() - 1);
+			}
+		}
+		
+		node.lent.set(node.lent.get() + 1);
+		let _lent = Lent(&node.lent);
+		f(&node.value)
+	}
+}
+
+fn pin6() {
+	println!("~pin6~");
+	use intrusive::{List, Node};
+	use std::pin::pin;
+	
+	let collect = |list: Pin<&List<&'static str>>| {
+		let mut values = Vec::new();
+		list.for_each(|v| values.push(*v));
+		values
+	};
+	
+	// pin!() pins the value on the stack, it can't be moved out of `list` any more
+	let list = pin!(List::new());
+	let list = list.into_ref();
+	
+	let mut a = pin!(Node::new("a"));
+	let mut c = Box::pin(Node::new("c"));
+	list.push_back(a.as_mut());
+	list.push_back(c.as_mut());
+	{
+		let b = pin!(Node::new("b"));
+		list.push_front(b);
+		assert_eq!(collect(list), vec!["b", "a", "c"]);
+		// b goes out of scope here, its Drop unlinks it
+	}
+	assert_eq!(collect(list), vec!["a", "c"]);
+	assert_eq!(list.len(), 2);
+	
+	// a boxed node may be moved around as a Pin<Box<_>>, the node itself stays put
+	let moved_c = c;
+	assert!(moved_c.is_linked());
+	drop(moved_c);
+	assert_eq!(collect(list), vec!["a"]);
+	
+	// error[E0277]: `PhantomPinned` cannot be unpinned
+	//let moved_a = std::mem::replace(a.as_mut().get_mut(), Node::new("z"));
+	
+	// a node can only be in one list at a time
+	let other = pin!(List::new());
+	// panicked at 'node is already linked into a list'
+	//other.as_ref().push_back(a.as_mut());
+	
+	assert_eq!(list.pop_front_with(|v| v.to_uppercase()), Some(String::from("A")));
+	assert!(!a.is_linked());
+	assert_eq!(a.value(), &"a");
+	assert_eq!(list.pop_back_with(|v| *v), None);
+	assert!(list.is_empty());
+	
+	// once unlinked, a node can be pushed again (into any list)
+	other.as_ref().push_back(a.as_mut());
+	assert_eq!(collect(other.as_ref()), vec!["a"]);
+	
+	let mut d = pin!(Node::new("d"));
+	{
+		let short_lived = pin!(List::new());
+		let short_lived = short_lived.into_ref();
+		short_lived.push_back(d.as_mut());
+		assert!(d.is_linked());
+		// nodes may outlive their list, dropping the list detaches them
+	}
+	assert!(!d.is_linked());
+}
+
 // Pin<P> prevents certain values (pointed to by pointers wrapped in Pin<P>) from being moved by 
 // making it impossible to call methods that require &mut T on them (like mem::swap).
 fn main() {
@@ -116,4 +479,6 @@ fn main() {
 	pin2();
 	pin3();
 	pin4();
+	pin5();
+	pin6();
 }
\ No newline at end of file
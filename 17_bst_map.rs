@@ -0,0 +1,803 @@
+use std::cmp::{self, Ordering};
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+use std::ptr;
+
+// An ordered map built as an AVL tree, in the same `Option<Box<Node>>`
+// style as `Link<T>` in 15_lists.rs.
+// AVL: the heights of the two subtrees of every node differ by at most one,
+// so the height of the tree (and the cost of a lookup) stays O(log n).
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+	key: K,
+	value: V,
+	left: Link<K, V>,
+	right: Link<K, V>,
+	// height of the subtree rooted at this node, a leaf has height 1
+	height: usize,
+	// number of nodes of the subtree rooted at this node
+	size: usize,
+}
+
+impl<K, V> Node<K, V> {
+	fn new(key: K, value: V) -> Self {
+		Node {
+			key,
+			value,
+			left: None,
+			right: None,
+			height: 1,
+			size: 1,
+		}
+	}
+
+	// recompute `height` and `size` after the children changed
+	fn update(&mut self) {
+		self.height = 1 + cmp::max(height(&self.left), height(&self.right));
+		self.size = 1 + size(&self.left) + size(&self.right);
+	}
+
+	fn balance_factor(&self) -> isize {
+		height(&self.left) as isize - height(&self.right) as isize
+	}
+}
+
+fn height<K, V>(link: &Link<K, V>) -> usize {
+	link.as_ref().map_or(0, |node| node.height)
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+	link.as_ref().map_or(0, |node| node.size)
+}
+
+//       node              left
+//      /    \            /    \
+//    left    c   =>     a     node
+//   /    \                   /    \
+//  a      b                 b      c
+fn rotate_right<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+	let mut left = node.left.take().expect("rotate right without left child");
+	node.left = left.right.take();
+	node.update();
+	left.right = Some(node);
+	left.update();
+	left
+}
+
+fn rotate_left<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+	let mut right = node.right.take().expect("rotate left without right child");
+	node.right = right.left.take();
+	node.update();
+	right.left = Some(node);
+	right.update();
+	right
+}
+
+// Restores the AVL property of `node`, assuming both subtrees are already AVL trees
+// and their heights differ by at most two. Returns the new root of the subtree.
+fn rebalance<K, V>(mut node: Box<Node<K, V>>) -> Box<Node<K, V>> {
+	node.update();
+
+	let bf = node.balance_factor();
+	if bf > 1 {
+		// left-right case: turn it into a left-left case first
+		if node.left.as_ref().map_or(0, |left| left.balance_factor()) < 0 {
+			node.left = node.left.take().map(rotate_left);
+		}
+		rotate_right(node)
+	} else if bf < -1 {
+		// right-left case
+		if node.right.as_ref().map_or(0, |right| right.balance_factor()) > 0 {
+			node.right = node.right.take().map(rotate_right);
+		}
+		rotate_left(node)
+	} else {
+		node
+	}
+}
+
+fn rebalance_link<K, V>(link: &mut Link<K, V>) {
+	if let Some(node) = link.take() {
+		*link = Some(rebalance(node));
+	}
+}
+
+fn insert_node<K: Ord, V>(link: &mut Link<K, V>, key: K, value: V) -> Option<V> {
+	let old_value = match link {
+		None => {
+			*link = Some(Box::new(Node::new(key, value)));
+			return None;
+		}
+		Some(node) => match key.cmp(&node.key) {
+			Ordering::Less => insert_node(&mut node.left, key, value),
+			Ordering::Greater => insert_node(&mut node.right, key, value),
+			// same key: only replace the value, the shape of the tree doesn't change
+			Ordering::Equal => return Some(mem::replace(&mut node.value, value)),
+		},
+	};
+
+	// a new node was hung somewhere below, walk back up and fix the heights
+	rebalance_link(link);
+	old_value
+}
+
+// Detaches the smallest node of the subtree, returns it with the rest of the subtree
+fn take_min<K, V>(mut node: Box<Node<K, V>>) -> (Box<Node<K, V>>, Link<K, V>) {
+	match node.left.take() {
+		None => {
+			let right = node.right.take();
+			(node, right)
+		}
+		Some(left) => {
+			let (min, rest) = take_min(left);
+			node.left = rest;
+			(min, Some(rebalance(node)))
+		}
+	}
+}
+
+fn remove_node<K: Ord, V>(link: &mut Link<K, V>, key: &K) -> Option<(K, V)> {
+	let removed = match link {
+		None => return None,
+		Some(node) => match key.cmp(&node.key) {
+			Ordering::Less => remove_node(&mut node.left, key),
+			Ordering::Greater => remove_node(&mut node.right, key),
+			Ordering::Equal => {
+				let mut boxed_node = link.take().unwrap();
+				*link = match (boxed_node.left.take(), boxed_node.right.take()) {
+					(None, None) => None,
+					(Some(child), None) | (None, Some(child)) => Some(child),
+					// replace the node by its in-order successor
+					(Some(left), Some(right)) => {
+						let (mut successor, rest) = take_min(right);
+						successor.left = Some(left);
+						successor.right = rest;
+						Some(rebalance(successor))
+					}
+				};
+				return Some((boxed_node.key, boxed_node.value));
+			}
+		},
+	};
+
+	if removed.is_some() {
+		rebalance_link(link);
+	}
+	removed
+}
+
+struct BstMap<K, V> {
+	root: Link<K, V>,
+}
+
+impl<K: Ord, V> BstMap<K, V> {
+	fn new() -> Self {
+		BstMap { root: None }
+	}
+
+	fn len(&self) -> usize {
+		size(&self.root)
+	}
+
+	fn is_empty(&self) -> bool {
+		self.root.is_none()
+	}
+
+	fn height(&self) -> usize {
+		height(&self.root)
+	}
+
+	// Returns the old value if the key was already present
+	fn insert(&mut self, key: K, value: V) -> Option<V> {
+		insert_node(&mut self.root, key, value)
+	}
+
+	fn remove(&mut self, key: &K) -> Option<V> {
+		self.remove_entry(key).map(|(_, value)| value)
+	}
+
+	fn remove_entry(&mut self, key: &K) -> Option<(K, V)> {
+		remove_node(&mut self.root, key)
+	}
+
+	fn get(&self, key: &K) -> Option<&V> {
+		let mut link = self.root.as_deref();
+		while let Some(node) = link {
+			link = match key.cmp(&node.key) {
+				Ordering::Less => node.left.as_deref(),
+				Ordering::Greater => node.right.as_deref(),
+				Ordering::Equal => return Some(&node.value),
+			};
+		}
+		None
+	}
+
+	fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+		let mut link = self.root.as_deref_mut();
+		while let Some(node) = link {
+			link = match key.cmp(&node.key) {
+				Ordering::Less => node.left.as_deref_mut(),
+				Ordering::Greater => node.right.as_deref_mut(),
+				Ordering::Equal => return Some(&mut node.value),
+			};
+		}
+		None
+	}
+
+	fn contains_key(&self, key: &K) -> bool {
+		self.get(key).is_some()
+	}
+
+	fn first_key_value(&self) -> Option<(&K, &V)> {
+		let mut node = self.root.as_deref()?;
+		while let Some(left) = node.left.as_deref() {
+			node = left;
+		}
+		Some((&node.key, &node.value))
+	}
+
+	fn last_key_value(&self) -> Option<(&K, &V)> {
+		let mut node = self.root.as_deref()?;
+		while let Some(right) = node.right.as_deref() {
+			node = right;
+		}
+		Some((&node.key, &node.value))
+	}
+
+	// Number of keys strictly less than `key`, i.e. the in-order position `key` has (or would have)
+	fn rank(&self, key: &K) -> usize {
+		let mut rank = 0;
+		let mut link = self.root.as_deref();
+		while let Some(node) = link {
+			link = match key.cmp(&node.key) {
+				Ordering::Less => node.left.as_deref(),
+				Ordering::Greater => {
+					rank += size(&node.left) + 1;
+					node.right.as_deref()
+				}
+				Ordering::Equal => return rank + size(&node.left),
+			};
+		}
+		rank
+	}
+
+	// The value at the given in-order position
+	fn nth_value_mut(&mut self, mut index: usize) -> Option<&mut V> {
+		let mut link = self.root.as_deref_mut();
+		while let Some(node) = link {
+			let left_size = size(&node.left);
+			link = match index.cmp(&left_size) {
+				Ordering::Less => node.left.as_deref_mut(),
+				Ordering::Greater => {
+					index -= left_size + 1;
+					node.right.as_deref_mut()
+				}
+				Ordering::Equal => return Some(&mut node.value),
+			};
+		}
+		None
+	}
+
+	fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+		// error[E0499]: cannot borrow `*self` as mutable more than once at a time
+		// returning a borrow from one branch of a `match` still keeps `self` borrowed
+		// in the other branch, so look the key up first and borrow mutably afterwards
+		if self.contains_key(&key) {
+			Entry::Occupied(OccupiedEntry { map: self, key })
+		} else {
+			Entry::Vacant(VacantEntry { map: self, key })
+		}
+	}
+
+	fn iter(&self) -> Iter<'_, K, V> {
+		let mut iter = Iter { stack: Vec::new() };
+		iter.push_left(self.root.as_deref());
+		iter
+	}
+
+	fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+		let mut iter = IterMut { stack: Vec::new() };
+		iter.push_left(self.root.as_deref_mut());
+		iter
+	}
+
+	// Iterates in order over the entries whose key lies in `range`, e.g. `map.range(3..=7)`
+	fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V> {
+		// 1. stack the path to the first key inside the range, like push_left does for iter()
+		let mut iter = Iter { stack: Vec::new() };
+		let mut link = self.root.as_deref();
+		while let Some(node) = link {
+			let after_start = match range.start_bound() {
+				Bound::Included(start) => node.key >= *start,
+				Bound::Excluded(start) => node.key > *start,
+				Bound::Unbounded => true,
+			};
+			if after_start {
+				iter.stack.push(node);
+				link = node.left.as_deref();
+			} else {
+				link = node.right.as_deref();
+			}
+		}
+
+		// 2. find the last key inside the range, iteration stops once it was yielded
+		let mut last = None;
+		let mut link = self.root.as_deref();
+		while let Some(node) = link {
+			let before_end = match range.end_bound() {
+				Bound::Included(end) => node.key <= *end,
+				Bound::Excluded(end) => node.key < *end,
+				Bound::Unbounded => true,
+			};
+			if before_end {
+				last = Some(&node.key);
+				link = node.right.as_deref();
+			} else {
+				link = node.left.as_deref();
+			}
+		}
+
+		// empty range, e.g. 5..3 or a range outside the keys of the map
+		match (iter.stack.last(), last) {
+			(Some(first), Some(last)) if first.key <= *last => {}
+			_ => iter.stack.clear(),
+		}
+
+		Range { iter, last }
+	}
+}
+
+impl<K: Ord, V> Default for BstMap<K, V> {
+	fn default() -> Self {
+		BstMap::new()
+	}
+}
+
+impl<K: Ord, V> Extend<(K, V)> for BstMap<K, V> {
+	fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+		for (key, value) in iter {
+			self.insert(key, value);
+		}
+	}
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for BstMap<K, V> {
+	fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+		let mut map = BstMap::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for BstMap<K, V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+enum Entry<'a, K: Ord, V> {
+	Occupied(OccupiedEntry<'a, K, V>),
+	Vacant(VacantEntry<'a, K, V>),
+}
+
+struct OccupiedEntry<'a, K: Ord, V> {
+	map: &'a mut BstMap<K, V>,
+	key: K,
+}
+
+struct VacantEntry<'a, K: Ord, V> {
+	map: &'a mut BstMap<K, V>,
+	key: K,
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+	fn key(&self) -> &K {
+		match self {
+			Entry::Occupied(entry) => entry.key(),
+			Entry::Vacant(entry) => entry.key(),
+		}
+	}
+
+	fn or_insert(self, default: V) -> &'a mut V {
+		match self {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => entry.insert(default),
+		}
+	}
+
+	fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+		match self {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => entry.insert(default()),
+		}
+	}
+
+	fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+		if let Entry::Occupied(ref mut entry) = self {
+			f(entry.get_mut());
+		}
+		self
+	}
+}
+
+impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
+	fn or_default(self) -> &'a mut V {
+		self.or_insert_with(V::default)
+	}
+}
+
+impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+	fn key(&self) -> &K {
+		&self.key
+	}
+
+	fn get(&self) -> &V {
+		self.map.get(&self.key).expect("occupied entry has a value")
+	}
+
+	fn get_mut(&mut self) -> &mut V {
+		self.map.get_mut(&self.key).expect("occupied entry has a value")
+	}
+
+	fn into_mut(self) -> &'a mut V {
+		self.map.get_mut(&self.key).expect("occupied entry has a value")
+	}
+
+	fn insert(&mut self, value: V) -> V {
+		mem::replace(self.get_mut(), value)
+	}
+
+	fn remove(self) -> V {
+		self.remove_entry().1
+	}
+
+	fn remove_entry(self) -> (K, V) {
+		self.map.remove_entry(&self.key).expect("occupied entry has a value")
+	}
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+	fn key(&self) -> &K {
+		&self.key
+	}
+
+	fn into_key(self) -> K {
+		self.key
+	}
+
+	fn insert(self, value: V) -> &'a mut V {
+		// The key is moved into the tree and the rotations may reshape the path to it,
+		// but they never change the in-order position of a node: remember the position
+		// before inserting and walk to it afterwards (`size` makes that O(log n)).
+		let index = self.map.rank(&self.key);
+		self.map.insert(self.key, value);
+		self.map.nth_value_mut(index).expect("the new entry was just inserted")
+	}
+}
+
+// In-order iteration: the stack holds the nodes whose left subtree was already
+// visited (or is being visited), the top of the stack is the next node to yield.
+struct Iter<'a, K, V> {
+	stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+	fn push_left(&mut self, mut link: Option<&'a Node<K, V>>) {
+		while let Some(node) = link {
+			self.stack.push(node);
+			link = node.left.as_deref();
+		}
+	}
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+	type Item = (&'a K, &'a V);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.stack.pop().map(|node| {
+			self.push_left(node.right.as_deref());
+			(&node.key, &node.value)
+		})
+	}
+}
+
+// A `&mut Node` can't be stacked while its right subtree is handed out later,
+// so split every node into disjoint borrows of its fields first.
+type SplitNode<'a, K, V> = (&'a K, &'a mut V, Option<&'a mut Node<K, V>>);
+
+struct IterMut<'a, K, V> {
+	stack: Vec<SplitNode<'a, K, V>>,
+}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+	fn push_left(&mut self, mut link: Option<&'a mut Node<K, V>>) {
+		while let Some(node) = link {
+			let Node { key, value, left, right, .. } = node;
+			self.stack.push((key, value, right.as_deref_mut()));
+			link = left.as_deref_mut();
+		}
+	}
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+	type Item = (&'a K, &'a mut V);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.stack.pop().map(|(key, value, right)| {
+			self.push_left(right);
+			(key, value)
+		})
+	}
+}
+
+struct IntoIter<K, V> {
+	stack: Vec<Box<Node<K, V>>>,
+}
+
+impl<K, V> IntoIter<K, V> {
+	fn push_left(&mut self, mut link: Link<K, V>) {
+		while let Some(mut node) = link {
+			link = node.left.take();
+			self.stack.push(node);
+		}
+	}
+}
+
+impl<K: Ord, V> IntoIterator for BstMap<K, V> {
+	type Item = (K, V);
+	type IntoIter = IntoIter<K, V>;
+
+	fn into_iter(mut self) -> Self::IntoIter {
+		let mut iter = IntoIter { stack: Vec::new() };
+		iter.push_left(self.root.take());
+		iter
+	}
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+	type Item = (K, V);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.stack.pop().map(|mut node| {
+			self.push_left(node.right.take());
+			(node.key, node.value)
+		})
+	}
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a BstMap<K, V> {
+	type Item = (&'a K, &'a V);
+	type IntoIter = Iter<'a, K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a mut BstMap<K, V> {
+	type Item = (&'a K, &'a mut V);
+	type IntoIter = IterMut<'a, K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter_mut()
+	}
+}
+
+struct Range<'a, K, V> {
+	iter: Iter<'a, K, V>,
+	// the last key inside the range
+	last: Option<&'a K>,
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+	type Item = (&'a K, &'a V);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (key, value) = self.iter.next()?;
+		// compare addresses, not values: K only needs Ord and the last node is unique
+		if self.last.is_some_and(|last| ptr::eq(key, last)) {
+			self.iter.stack.clear();
+		}
+		Some((key, value))
+	}
+}
+
+// Checks the ordering, the cached heights/sizes and the AVL property of every node
+fn assert_avl<K: Ord, V>(link: &Link<K, V>) -> usize {
+	match link {
+		None => 0,
+		Some(node) => {
+			if let Some(left) = node.left.as_ref() {
+				assert!(left.key < node.key);
+			}
+			if let Some(right) = node.right.as_ref() {
+				assert!(right.key > node.key);
+			}
+			let left_height = assert_avl(&node.left);
+			let right_height = assert_avl(&node.right);
+			assert!(node.balance_factor().abs() <= 1);
+			assert_eq!(node.height, 1 + cmp::max(left_height, right_height));
+			assert_eq!(node.size, 1 + size(&node.left) + size(&node.right));
+			node.height
+		}
+	}
+}
+
+// A tiny linear congruential generator, enough to shuffle test data without external crates
+struct Lcg(u64);
+
+impl Lcg {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+		self.0 >> 33
+	}
+}
+
+fn test_basics() {
+	let mut map = BstMap::new();
+	assert!(map.is_empty());
+	assert_eq!(map.get(&1), None);
+	assert_eq!(map.remove(&1), None);
+
+	assert_eq!(map.insert(2, "two"), None);
+	assert_eq!(map.insert(1, "one"), None);
+	assert_eq!(map.insert(3, "three"), None);
+	assert_eq!(map.insert(2, "TWO"), Some("two"));
+	assert_eq!(map.len(), 3);
+
+	assert_eq!(map.get(&2), Some(&"TWO"));
+	assert!(map.contains_key(&3));
+	assert!(!map.contains_key(&4));
+
+	if let Some(v) = map.get_mut(&1) {
+		*v = "ONE";
+	}
+	assert_eq!(map.get(&1), Some(&"ONE"));
+
+	assert_eq!(map.first_key_value(), Some((&1, &"ONE")));
+	assert_eq!(map.last_key_value(), Some((&3, &"three")));
+
+	assert_eq!(map.remove(&2), Some("TWO"));
+	assert_eq!(map.remove(&2), None);
+	assert_eq!(map.len(), 2);
+	assert_eq!(format!("{:?}", map), r#"{1: "ONE", 3: "three"}"#);
+	assert_avl(&map.root);
+}
+
+fn test_balance() {
+	// sorted inserts degrade a plain BST into a list, an AVL tree stays logarithmic
+	let mut map = BstMap::new();
+	for i in 0..1024 {
+		map.insert(i, i * i);
+	}
+	assert_eq!(map.len(), 1024);
+	assert_avl(&map.root);
+	assert!(map.height() <= 11, "height {} is not logarithmic", map.height());
+
+	for i in (0..1024).filter(|i| i % 3 != 0) {
+		assert_eq!(map.remove(&i), Some(i * i));
+	}
+	assert_eq!(map.len(), 342);
+	assert_avl(&map.root);
+	assert!(map.height() <= 10, "height {} is not logarithmic", map.height());
+}
+
+fn test_against_btree_map() {
+	use std::collections::BTreeMap;
+
+	let mut rng = Lcg(42);
+	let mut map = BstMap::new();
+	let mut expected = BTreeMap::new();
+
+	for _ in 0..5000 {
+		let key = rng.next() % 500;
+		match rng.next() % 3 {
+			0 => assert_eq!(map.remove(&key), expected.remove(&key)),
+			_ => assert_eq!(map.insert(key, key * 10), expected.insert(key, key * 10)),
+		}
+	}
+
+	assert_avl(&map.root);
+	assert_eq!(map.len(), expected.len());
+	assert!(map.iter().eq(expected.iter()));
+}
+
+fn test_iters() {
+	let mut map: BstMap<i32, i32> = vec![(5, 50), (3, 30), (8, 80), (1, 10), (4, 40)].into_iter().collect();
+
+	let keys: Vec<_> = map.iter().map(|(k, _)| *k).collect();
+	assert_eq!(keys, vec![1, 3, 4, 5, 8]);
+
+	for (k, v) in map.iter_mut() {
+		*v += k;
+	}
+	for (k, v) in &mut map {
+		*v += k;
+	}
+	assert_eq!(map.iter().map(|(_, v)| *v).collect::<Vec<_>>(), vec![12, 36, 48, 60, 96]);
+
+	let mut iter = map.into_iter();
+	assert_eq!(iter.next(), Some((1, 12)));
+	assert_eq!(iter.next(), Some((3, 36)));
+	assert_eq!(iter.next(), Some((4, 48)));
+	assert_eq!(iter.next(), Some((5, 60)));
+	assert_eq!(iter.next(), Some((8, 96)));
+	assert_eq!(iter.next(), None);
+}
+
+fn test_range() {
+	let map: BstMap<i32, ()> = (0..20).map(|i| (i * 2, ())).collect();
+	let keys = |range: Range<i32, ()>| range.map(|(k, _)| *k).collect::<Vec<_>>();
+
+	assert_eq!(keys(map.range(3..9)), vec![4, 6, 8]);
+	assert_eq!(keys(map.range(4..=8)), vec![4, 6, 8]);
+	assert_eq!(keys(map.range(4..8)), vec![4, 6]);
+	assert_eq!(keys(map.range((Bound::Excluded(4), Bound::Included(8)))), vec![6, 8]);
+	assert_eq!(keys(map.range(..3)), vec![0, 2]);
+	assert_eq!(keys(map.range(35..)), vec![36, 38]);
+	assert_eq!(keys(map.range(..)).len(), 20);
+
+	// empty ranges
+	assert_eq!(keys(map.range(5..6)), vec![]);
+	assert_eq!(keys(map.range((Bound::Included(9), Bound::Excluded(3)))), vec![]);
+	assert_eq!(keys(map.range(100..)), vec![]);
+	assert_eq!(keys(map.range(..0)), vec![]);
+}
+
+fn test_entry() {
+	let mut counts = BstMap::new();
+	for word in "the quick fox jumps over the lazy dog the end".split_whitespace() {
+		*counts.entry(word).or_insert(0) += 1;
+	}
+	assert_eq!(counts.get(&"the"), Some(&3));
+	assert_eq!(counts.get(&"fox"), Some(&1));
+	assert_eq!(counts.len(), 8);
+	assert_avl(&counts.root);
+
+	counts.entry("fox").and_modify(|v| *v += 10).or_insert(0);
+	counts.entry("cat").and_modify(|v| *v += 10).or_insert(0);
+	assert_eq!(counts.get(&"fox"), Some(&11));
+	assert_eq!(counts.get(&"cat"), Some(&0));
+
+	assert_eq!(counts.entry("dog").key(), &"dog");
+	match counts.entry("dog") {
+		Entry::Occupied(mut entry) => {
+			assert_eq!(entry.get(), &1);
+			assert_eq!(entry.insert(7), 1);
+			assert_eq!(entry.remove_entry(), ("dog", 7));
+		}
+		Entry::Vacant(_) => unreachable!(),
+	}
+	match counts.entry("dog") {
+		Entry::Occupied(_) => unreachable!(),
+		Entry::Vacant(entry) => assert_eq!(entry.into_key(), "dog"),
+	}
+
+	// or_insert must hand back the new value even when the insert rotated the tree
+	let mut map: BstMap<i32, Vec<i32>> = BstMap::new();
+	for i in 0..100 {
+		map.entry(i).or_default().push(i);
+		map.entry(i / 2).or_insert_with(|| Vec::with_capacity(3)).push(-i);
+	}
+	assert_avl(&map.root);
+	assert_eq!(map.get(&10), Some(&vec![10, -20, -21]));
+	assert_eq!(map.get(&99), Some(&vec![99]));
+
+	if let Entry::Occupied(entry) = map.entry(99) {
+		assert_eq!(entry.remove(), vec![99]);
+	}
+	assert!(!map.contains_key(&99));
+	assert_avl(&map.root);
+}
+
+fn main() {
+	test_basics();
+	test_balance();
+	test_against_btree_map();
+	test_iters();
+	test_range();
+	test_entry();
+}
@@ -0,0 +1,455 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::mem::ManuallyDrop;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn print_knowledage_point(s: &str) {
+	println!("~~~~~~~{}~~~~~~~", s);
+}
+
+/*
+An `async fn` is compiled into a state machine which implements `Future`.
+Its local variables live inside that state machine, so a borrow held across an `.await`
+is a pointer from the future into itself: the future is self-referential, just like
+`Unmovable` in 16_pin.rs. That's why `Future::poll` takes `self: Pin<&mut Self>`:
+once a future was polled it must never move again.
+
+Nothing polls a future by itself, an executor has to:
+	1. poll the future with a `Context` carrying a `Waker`
+	2. the future returns Poll::Pending and keeps a clone of the waker
+	3. when it can make progress (a timer fired, data arrived...) it calls `wake()`
+	4. the waker puts the task back into the executor's queue, goto 1
+*/
+
+// ---- Waker ----
+// A waker only carries the id of its task and the sending half of the run queue.
+// `Waker` is Send + Sync, it may be woken from any thread (e.g. the timer thread)
+struct WakeHandle {
+	id: usize,
+	queue: Mutex<Sender<usize>>,
+}
+
+impl WakeHandle {
+	fn schedule(&self) {
+		// the executor may already be gone, then there is nothing left to run
+		let _ = self.queue.lock().unwrap().send(self.id);
+	}
+}
+
+// A RawWaker is a data pointer plus a hand-written vtable, here the data pointer
+// is an `Arc<WakeHandle>` turned into a raw pointer by `Arc::into_raw`.
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone_waker, wake, wake_by_ref, drop_waker);
+
+fn raw_waker(handle: Arc<WakeHandle>) -> RawWaker {
+	RawWaker::new(Arc::into_raw(handle) as *const (), &VTABLE)
+}
+
+fn waker(handle: Arc<WakeHandle>) -> Waker {
+	// the vtable functions below respect the RawWaker contract
+	unsafe { Waker::from_raw(raw_waker(handle)) }
+}
+
+// ManuallyDrop: the functions taking `data` by reference must not decrement the count
+unsafe fn clone_waker(data: *const ()) -> RawWaker {
+	let handle = ManuallyDrop::new(unsafe { Arc::from_raw(data as *const WakeHandle) });
+	raw_waker(Arc::clone(&handle))
+}
+
+// consumes the waker
+unsafe fn wake(data: *const ()) {
+	let handle = unsafe { Arc::from_raw(data as *const WakeHandle) };
+	handle.schedule();
+}
+
+unsafe fn wake_by_ref(data: *const ()) {
+	let handle = ManuallyDrop::new(unsafe { Arc::from_raw(data as *const WakeHandle) });
+	handle.schedule();
+}
+
+unsafe fn drop_waker(data: *const ()) {
+	drop(unsafe { Arc::from_raw(data as *const WakeHandle) });
+}
+
+// ---- Executor ----
+// The futures are not Send: they are created, stored and polled on this thread only.
+// Just the task ids travel through the channel.
+type LocalFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+#[derive(Clone)]
+struct Spawner {
+	new_tasks: Rc<RefCell<Vec<LocalFuture>>>,
+}
+
+impl Spawner {
+	fn spawn<F>(&self, future: F)
+		where F: Future<Output = ()> + 'static {
+
+		// Box::pin: the future is pinned on the heap before it is polled for the first time
+		self.new_tasks.borrow_mut().push(Box::pin(future));
+	}
+}
+
+struct Executor {
+	spawner: Spawner,
+	tasks: HashMap<usize, LocalFuture>,
+	next_id: usize,
+	sender: Sender<usize>,
+	receiver: Receiver<usize>,
+}
+
+impl Executor {
+	fn new() -> Self {
+		let (sender, receiver) = mpsc::channel();
+		Executor {
+			spawner: Spawner { new_tasks: Rc::new(RefCell::new(Vec::new())) },
+			tasks: HashMap::new(),
+			next_id: 0,
+			sender,
+			receiver,
+		}
+	}
+
+	// A spawner can be moved into tasks to spawn more tasks while running
+	fn spawner(&self) -> Spawner {
+		self.spawner.clone()
+	}
+
+	fn spawn<F>(&self, future: F)
+		where F: Future<Output = ()> + 'static {
+
+		self.spawner.spawn(future);
+	}
+
+	// Moves the newly spawned futures into the task table and schedules them once
+	fn admit_new_tasks(&mut self) {
+		let new_tasks: Vec<_> = self.spawner.new_tasks.borrow_mut().drain(..).collect();
+		for future in new_tasks {
+			let id = self.next_id;
+			self.next_id += 1;
+			self.tasks.insert(id, future);
+			self.sender.send(id).unwrap();
+		}
+	}
+
+	// Runs until every task completed.
+	// Blocks forever if the remaining tasks wait for a wake-up that never comes.
+	fn run(&mut self) {
+		loop {
+			self.admit_new_tasks();
+			if self.tasks.is_empty() {
+				break;
+			}
+
+			// sleeps until some waker sends the id of its task
+			let id = self.receiver.recv().unwrap();
+
+			// a finished task may still be woken by a stale waker
+			let future = match self.tasks.get_mut(&id) {
+				Some(future) => future,
+				None => continue,
+			};
+
+			let waker = waker(Arc::new(WakeHandle {
+				id,
+				queue: Mutex::new(self.sender.clone()),
+			}));
+			let mut cx = Context::from_waker(&waker);
+
+			if future.as_mut().poll(&mut cx).is_ready() {
+				self.tasks.remove(&id);
+			}
+		}
+	}
+}
+
+fn block_on<F: Future + 'static>(future: F) -> F::Output {
+	let mut executor = Executor::new();
+	let output = Rc::new(RefCell::new(None));
+
+	let slot = Rc::clone(&output);
+	executor.spawn(async move {
+		*slot.borrow_mut() = Some(future.await);
+	});
+	executor.run();
+
+	let output = output.borrow_mut().take();
+	output.expect("the future ran to completion")
+}
+
+// ---- Leaf futures ----
+struct TimerState {
+	done: bool,
+	waker: Option<Waker>,
+}
+
+// Completes after the given duration, a helper thread sleeps and then wakes the task
+struct Timer {
+	state: Arc<Mutex<TimerState>>,
+}
+
+impl Timer {
+	fn after(duration: Duration) -> Timer {
+		let state = Arc::new(Mutex::new(TimerState { done: false, waker: None }));
+
+		let thread_state = Arc::clone(&state);
+		thread::spawn(move || {
+			thread::sleep(duration);
+			let mut state = thread_state.lock().unwrap();
+			state.done = true;
+			if let Some(waker) = state.waker.take() {
+				waker.wake();
+			}
+		});
+
+		Timer { state }
+	}
+}
+
+// Timer has no self-references, it is Unpin and can ignore the Pin
+impl Future for Timer {
+	type Output = ();
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let mut state = self.state.lock().unwrap();
+		if state.done {
+			Poll::Ready(())
+		} else {
+			// the task may have been polled with another waker since the last time
+			state.waker = Some(cx.waker().clone());
+			Poll::Pending
+		}
+	}
+}
+
+// Returns Pending once, so other tasks get a chance to run
+struct YieldNow(bool);
+
+fn yield_now() -> YieldNow {
+	YieldNow(false)
+}
+
+impl Future for YieldNow {
+	type Output = ();
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		if self.0 {
+			Poll::Ready(())
+		} else {
+			self.0 = true;
+			cx.waker().wake_by_ref();
+			Poll::Pending
+		}
+	}
+}
+
+// ---- Combinators ----
+// Join owns two futures which may be !Unpin (async fn), so it can only poll them
+// through a Pin<&mut A> derived from its own Pin<&mut Self>: "pin projection".
+struct Join<A: Future, B: Future> {
+	a: A,
+	b: B,
+	a_output: Option<A::Output>,
+	b_output: Option<B::Output>,
+}
+
+fn join<A: Future, B: Future>(a: A, b: B) -> Join<A, B> {
+	Join { a, b, a_output: None, b_output: None }
+}
+
+impl<A: Future, B: Future> Future for Join<A, B> {
+	type Output = (A::Output, B::Output);
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		// error[E0277]: `A` cannot be unpinned
+		//let this = self.get_mut();
+		// `a` and `b` are structurally pinned: they are never moved out of `this`
+		// (Join has no Drop impl and no method moving them), only re-pinned in place.
+		// The outputs are plain data and may be moved freely.
+		let this = unsafe { self.get_unchecked_mut() };
+
+		if this.a_output.is_none() {
+			if let Poll::Ready(output) = unsafe { Pin::new_unchecked(&mut this.a) }.poll(cx) {
+				this.a_output = Some(output);
+			}
+		}
+		if this.b_output.is_none() {
+			if let Poll::Ready(output) = unsafe { Pin::new_unchecked(&mut this.b) }.poll(cx) {
+				this.b_output = Some(output);
+			}
+		}
+
+		if this.a_output.is_some() && this.b_output.is_some() {
+			Poll::Ready((this.a_output.take().unwrap(), this.b_output.take().unwrap()))
+		} else {
+			Poll::Pending
+		}
+	}
+}
+
+#[derive(Debug, PartialEq)]
+enum Either<L, R> {
+	Left(L),
+	Right(R),
+}
+
+// Completes with the output of whichever future finishes first, the other one is dropped
+struct Select<A, B> {
+	a: A,
+	b: B,
+}
+
+fn select<A: Future, B: Future>(a: A, b: B) -> Select<A, B> {
+	Select { a, b }
+}
+
+impl<A: Future, B: Future> Future for Select<A, B> {
+	type Output = Either<A::Output, B::Output>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		// same structural pinning as in Join
+		let this = unsafe { self.get_unchecked_mut() };
+
+		if let Poll::Ready(output) = unsafe { Pin::new_unchecked(&mut this.a) }.poll(cx) {
+			return Poll::Ready(Either::Left(output));
+		}
+		if let Poll::Ready(output) = unsafe { Pin::new_unchecked(&mut this.b) }.poll(cx) {
+			return Poll::Ready(Either::Right(output));
+		}
+		Poll::Pending
+	}
+}
+
+// ---- Examples ----
+async fn add_later(a: u32, b: u32, millis: u64) -> u32 {
+	Timer::after(Duration::from_millis(millis)).await;
+	a + b
+}
+
+fn executor_block_on() {
+	print_knowledage_point("executor block on");
+
+	let sum = block_on(add_later(1, 2, 10));
+	println!("1 + 2 = {}", sum);
+	assert_eq!(sum, 3);
+
+	// an async block is a future too
+	let len = block_on(async {
+		let a = add_later(1, 2, 5).await;
+		let b = add_later(a, 4, 5).await;
+		format!("{}", b).len()
+	});
+	assert_eq!(len, 1);
+}
+
+fn executor_spawn() {
+	print_knowledage_point("executor spawn");
+
+	let log = Rc::new(RefCell::new(Vec::new()));
+	let mut executor = Executor::new();
+
+	for &(name, millis) in [("slow", 60), ("fast", 10)].iter() {
+		let log = Rc::clone(&log);
+		executor.spawn(async move {
+			Timer::after(Duration::from_millis(millis)).await;
+			println!("{} timer fired", name);
+			log.borrow_mut().push(name);
+		});
+	}
+
+	// tasks may spawn tasks
+	let spawner = executor.spawner();
+	let inner_log = Rc::clone(&log);
+	executor.spawn(async move {
+		inner_log.borrow_mut().push("parent");
+		spawner.spawn(async move {
+			yield_now().await;
+			inner_log.borrow_mut().push("child");
+		});
+	});
+
+	executor.run();
+	// the timers fire when their threads get to it, only the parent is surely before its child
+	let log = log.borrow();
+	let position = |name| log.iter().position(|entry| *entry == name);
+	assert_eq!(log.len(), 4);
+	assert!(position("parent") < position("child"));
+	assert!(position("fast").is_some() && position("slow").is_some());
+}
+
+fn executor_join() {
+	print_knowledage_point("executor join");
+
+	// both timers run concurrently: ~100ms rather than 200ms
+	let start = Instant::now();
+	let (a, b) = block_on(join(add_later(1, 1, 100), add_later(2, 2, 100)));
+	let elapsed = start.elapsed();
+	println!("joined {} and {} in {:?}", a, b, elapsed);
+
+	assert_eq!((a, b), (2, 4));
+	assert!(elapsed >= Duration::from_millis(100));
+
+	// the proof doesn't need a clock: the second one starts before the first one ends
+	let log = Rc::new(RefCell::new(Vec::new()));
+	let logged = |name: &'static str, log: Rc<RefCell<Vec<String>>>| async move {
+		log.borrow_mut().push(format!("{} start", name));
+		Timer::after(Duration::from_millis(10)).await;
+		log.borrow_mut().push(format!("{} end", name));
+	};
+	block_on(join(logged("a", Rc::clone(&log)), logged("b", Rc::clone(&log))));
+	assert_eq!(log.borrow()[..2], ["a start", "b start"]);
+}
+
+fn executor_select() {
+	print_knowledage_point("executor select");
+
+	// select as a timeout
+	let result = block_on(select(add_later(1, 1, 500), Timer::after(Duration::from_millis(20))));
+	println!("timeout result: {:?}", result);
+	assert_eq!(result, Either::Right(()));
+
+	let result = block_on(select(add_later(1, 1, 10), Timer::after(Duration::from_millis(500))));
+	assert_eq!(result, Either::Left(2));
+}
+
+fn self_referential_future() {
+	print_knowledage_point("self referential future");
+
+	let future = async {
+		let data = [1, 2, 3];
+		// `slice` points into `data`, both are stored inside the future across the `.await`
+		let slice = &data[1..];
+		Timer::after(Duration::from_millis(1)).await;
+		slice.iter().sum::<i32>()
+	};
+
+	// error[E0277]: `{async block@...}` cannot be unpinned
+	// moving the future after the first poll would leave `slice` dangling
+	//let mut future = future;
+	//let _ = Pin::new(&mut future);
+
+	// Box::pin (or pin!) is the way to poll it
+	let mut pinned = Box::pin(future);
+	let waker = waker(Arc::new(WakeHandle { id: 0, queue: Mutex::new(mpsc::channel().0) }));
+	let mut cx = Context::from_waker(&waker);
+	// the timer hasn't fired yet
+	assert_eq!(pinned.as_mut().poll(&mut cx), Poll::Pending);
+
+	// the pinned box can be moved around, its content stays put
+	let moved = pinned;
+	assert_eq!(block_on(moved), 5);
+}
+
+fn main() {
+	executor_block_on();
+	executor_spawn();
+	executor_join();
+	executor_select();
+	self_referential_future();
+}
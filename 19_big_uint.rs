@@ -0,0 +1,463 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::str::FromStr;
+
+fn print_knowledage_point(s: &str) {
+	println!("~~~~~~~{}~~~~~~~", s);
+}
+
+/*
+An arbitrary-precision unsigned integer.
+The number is stored in base 2^32: digits[0] is the least significant "digit" (limb).
+The vector is kept normalized: no trailing (most significant) zero limbs, so zero is an empty vector
+and two equal numbers always have the same representation (derive(PartialEq) is enough).
+*/
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+struct BigUint {
+	digits: Vec<u32>,
+}
+
+const BASE_BITS: usize = 32;
+// the largest power of ten fitting into a limb, used to convert from/to decimal strings
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_BASE_DIGITS: usize = 9;
+
+impl BigUint {
+	fn zero() -> Self {
+		BigUint { digits: Vec::new() }
+	}
+
+	fn is_zero(&self) -> bool {
+		self.digits.is_empty()
+	}
+
+	fn normalize(mut self) -> Self {
+		while let Some(&0) = self.digits.last() {
+			self.digits.pop();
+		}
+		self
+	}
+
+	fn bits(&self) -> usize {
+		match self.digits.last() {
+			Some(&top) => self.digits.len() * BASE_BITS - top.leading_zeros() as usize,
+			None => 0,
+		}
+	}
+
+	fn bit(&self, i: usize) -> bool {
+		self.digits.get(i / BASE_BITS).is_some_and(|&d| d >> (i % BASE_BITS) & 1 == 1)
+	}
+
+	// self = self * 2 + bit
+	fn shl1_add(&mut self, bit: bool) {
+		let mut carry = bit as u32;
+		for d in self.digits.iter_mut() {
+			let new_carry = *d >> (BASE_BITS - 1);
+			*d = *d << 1 | carry;
+			carry = new_carry;
+		}
+		if carry != 0 {
+			self.digits.push(carry);
+		}
+	}
+
+	// self = self * m + a
+	fn mul_small_add(&mut self, m: u32, a: u32) {
+		let mut carry = a as u64;
+		for d in self.digits.iter_mut() {
+			let v = *d as u64 * m as u64 + carry;
+			*d = v as u32;
+			carry = v >> BASE_BITS;
+		}
+		if carry != 0 {
+			self.digits.push(carry as u32);
+		}
+	}
+
+	// Division by a single limb, returns (quotient, remainder)
+	fn div_rem_small(&self, divisor: u32) -> (BigUint, u32) {
+		assert!(divisor != 0, "attempt to divide by zero");
+
+		let mut quotient = vec![0; self.digits.len()];
+		let mut rem = 0u64;
+		for (i, &d) in self.digits.iter().enumerate().rev() {
+			let cur = rem << BASE_BITS | d as u64;
+			quotient[i] = (cur / divisor as u64) as u32;
+			rem = cur % divisor as u64;
+		}
+		(BigUint { digits: quotient }.normalize(), rem as u32)
+	}
+
+	// Binary long division, one bit of the quotient per step: O(bits * limbs),
+	// slow compared to Knuth's algorithm D, but short and easy to check.
+	fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+		assert!(!divisor.is_zero(), "attempt to divide by zero");
+
+		if self < divisor {
+			return (BigUint::zero(), self.clone());
+		}
+		if divisor.digits.len() == 1 {
+			let (quotient, rem) = self.div_rem_small(divisor.digits[0]);
+			return (quotient, BigUint::from(rem as u64));
+		}
+
+		let mut quotient = vec![0; self.digits.len()];
+		let mut rem = BigUint::zero();
+		for i in (0..self.bits()).rev() {
+			rem.shl1_add(self.bit(i));
+			if rem >= *divisor {
+				rem = &rem - divisor;
+				quotient[i / BASE_BITS] |= 1 << (i % BASE_BITS);
+			}
+		}
+		(BigUint { digits: quotient }.normalize(), rem)
+	}
+
+	fn checked_sub(&self, rhs: &BigUint) -> Option<BigUint> {
+		if self < rhs {
+			return None;
+		}
+
+		let mut digits = Vec::with_capacity(self.digits.len());
+		let mut borrow = false;
+		for (i, &a) in self.digits.iter().enumerate() {
+			let b = rhs.digits.get(i).copied().unwrap_or(0);
+			let (d, borrow1) = a.overflowing_sub(b);
+			let (d, borrow2) = d.overflowing_sub(borrow as u32);
+			digits.push(d);
+			borrow = borrow1 || borrow2;
+		}
+		Some(BigUint { digits }.normalize())
+	}
+
+	fn pow(&self, mut exp: u32) -> BigUint {
+		let mut base = self.clone();
+		let mut result = BigUint::from(1);
+		while exp > 0 {
+			if exp & 1 == 1 {
+				result = &result * &base;
+			}
+			base = &base * &base;
+			exp >>= 1;
+		}
+		result
+	}
+}
+
+impl From<u64> for BigUint {
+	fn from(v: u64) -> Self {
+		BigUint { digits: vec![v as u32, (v >> BASE_BITS) as u32] }.normalize()
+	}
+}
+
+impl Ord for BigUint {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// normalized: more limbs means a bigger number
+		self.digits.len().cmp(&other.digits.len())
+			.then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+	}
+}
+
+impl PartialOrd for BigUint {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+// The operators are implemented on references, so `&a + &b` doesn't consume the operands.
+// The owned versions (`a + b`) are forwarded to them by the macro below.
+impl<'a> Add<&'a BigUint> for &'a BigUint {
+	type Output = BigUint;
+
+	fn add(self, rhs: &BigUint) -> BigUint {
+		let (long, short) = if self.digits.len() >= rhs.digits.len() { (self, rhs) } else { (rhs, self) };
+
+		let mut digits = Vec::with_capacity(long.digits.len() + 1);
+		let mut carry = 0u64;
+		for (i, &a) in long.digits.iter().enumerate() {
+			let b = short.digits.get(i).copied().unwrap_or(0);
+			let sum = a as u64 + b as u64 + carry;
+			digits.push(sum as u32);
+			carry = sum >> BASE_BITS;
+		}
+		if carry != 0 {
+			digits.push(carry as u32);
+		}
+		BigUint { digits }
+	}
+}
+
+// Like the primitive unsigned types, a negative result panics
+impl<'a> Sub<&'a BigUint> for &'a BigUint {
+	type Output = BigUint;
+
+	fn sub(self, rhs: &BigUint) -> BigUint {
+		self.checked_sub(rhs).expect("attempt to subtract with overflow")
+	}
+}
+
+impl<'a> Mul<&'a BigUint> for &'a BigUint {
+	type Output = BigUint;
+
+	// schoolbook multiplication, O(n * m)
+	fn mul(self, rhs: &BigUint) -> BigUint {
+		if self.is_zero() || rhs.is_zero() {
+			return BigUint::zero();
+		}
+
+		let mut digits = vec![0u32; self.digits.len() + rhs.digits.len()];
+		for (i, &a) in self.digits.iter().enumerate() {
+			let mut carry = 0u64;
+			for (j, &b) in rhs.digits.iter().enumerate() {
+				// (2^32-1)^2 + 2 * (2^32-1) still fits into a u64
+				let v = a as u64 * b as u64 + digits[i + j] as u64 + carry;
+				digits[i + j] = v as u32;
+				carry = v >> BASE_BITS;
+			}
+			digits[i + rhs.digits.len()] = carry as u32;
+		}
+		BigUint { digits }.normalize()
+	}
+}
+
+impl<'a> Div<&'a BigUint> for &'a BigUint {
+	type Output = BigUint;
+
+	fn div(self, rhs: &BigUint) -> BigUint {
+		self.div_rem(rhs).0
+	}
+}
+
+impl<'a> Rem<&'a BigUint> for &'a BigUint {
+	type Output = BigUint;
+
+	fn rem(self, rhs: &BigUint) -> BigUint {
+		self.div_rem(rhs).1
+	}
+}
+
+macro_rules! forward_owned_binop {
+	($($imp: ident, $method: ident);*) => {
+		$(
+			impl $imp for BigUint {
+				type Output = BigUint;
+
+				fn $method(self, rhs: BigUint) -> BigUint {
+					(&self).$method(&rhs)
+				}
+			}
+		)*
+	};
+}
+
+forward_owned_binop!(Add, add; Sub, sub; Mul, mul; Div, div; Rem, rem);
+
+impl fmt::Display for BigUint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		// split into base 10^9 chunks, least significant first
+		let mut chunks = Vec::new();
+		let mut rest = self.clone();
+		while !rest.is_zero() {
+			let (quotient, rem) = rest.div_rem_small(DECIMAL_BASE);
+			chunks.push(rem);
+			rest = quotient;
+		}
+
+		let mut s = match chunks.pop() {
+			Some(top) => top.to_string(),
+			None => String::from("0"),
+		};
+		for chunk in chunks.iter().rev() {
+			s.push_str(&format!("{:0width$}", chunk, width = DECIMAL_BASE_DIGITS));
+		}
+
+		// honours width, fill, alignment, `+` and `0` flags like the primitive integers
+		f.pad_integral(true, "", &s)
+	}
+}
+
+impl fmt::Debug for BigUint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "BigUint({})", self)
+	}
+}
+
+// Mirrors std::num::ParseIntError
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseBigUintError {
+	Empty,
+	InvalidDigit,
+}
+
+impl fmt::Display for ParseBigUintError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ParseBigUintError::Empty => write!(f, "cannot parse integer from empty string"),
+			ParseBigUintError::InvalidDigit => write!(f, "invalid digit found in string"),
+		}
+	}
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+impl FromStr for BigUint {
+	type Err = ParseBigUintError;
+
+	// Accepts the same input as `"123".parse::<u64>()`: decimal digits with an optional leading `+`
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s.is_empty() {
+			return Err(ParseBigUintError::Empty);
+		}
+		// a bare "+" is an invalid digit, not an empty string
+		let digits = s.strip_prefix('+').unwrap_or(s);
+		if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+			return Err(ParseBigUintError::InvalidDigit);
+		}
+
+		// the first chunk is the short one, so all the others have exactly 9 digits
+		let first_len = match digits.len() % DECIMAL_BASE_DIGITS {
+			0 => DECIMAL_BASE_DIGITS,
+			n => n,
+		};
+		let mut value = BigUint::zero();
+		let mut multiplier = 10u32.pow(first_len as u32);
+		let mut start = 0;
+		let mut end = first_len;
+		while start < digits.len() {
+			// all ASCII digits, can't fail or overflow
+			let chunk: u32 = digits[start..end].parse().unwrap();
+			value.mul_small_add(multiplier, chunk);
+			multiplier = DECIMAL_BASE;
+			start = end;
+			end += DECIMAL_BASE_DIGITS;
+		}
+		Ok(value.normalize())
+	}
+}
+
+// Same as `Fibonacci` in 09_trait.rs, but the terms never overflow
+struct BigFibonacci {
+	curr: BigUint,
+	next: BigUint,
+}
+
+impl Iterator for BigFibonacci {
+	type Item = BigUint;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let new_next = &self.curr + &self.next;
+
+		// mem::replace moves the values around instead of cloning both
+		self.curr = mem::replace(&mut self.next, new_next);
+
+		Some(self.curr.clone())
+	}
+}
+
+fn big_fibonacci() -> BigFibonacci {
+	BigFibonacci { curr: BigUint::zero(), next: BigUint::from(1) }
+}
+
+fn factorial(n: u32) -> BigUint {
+	(1..=n as u64).fold(BigUint::from(1), |acc, i| &acc * &BigUint::from(i))
+}
+
+fn big_uint_conversion() {
+	print_knowledage_point("big uint conversion");
+
+	assert_eq!(BigUint::from(0).to_string(), "0");
+	assert_eq!(BigUint::from(u64::MAX).to_string(), "18446744073709551615");
+	assert_eq!(BigUint::from(1_000_000_000).to_string(), "1000000000");
+
+	let parsed: BigUint = "340282366920938463463374607431768211456".parse().unwrap();
+	let turbo_parsed = "1".parse::<BigUint>().unwrap();
+	println!("2^128 = {}", parsed);
+	assert_eq!(parsed, BigUint::from(2).pow(128));
+	assert_eq!((&parsed - &turbo_parsed).to_string(), u128::MAX.to_string());
+
+	assert_eq!("+42".parse::<BigUint>(), Ok(BigUint::from(42)));
+	assert_eq!("000000000000000000042".parse::<BigUint>(), Ok(BigUint::from(42)));
+	assert_eq!("".parse::<BigUint>(), Err(ParseBigUintError::Empty));
+	assert_eq!("+".parse::<BigUint>(), Err(ParseBigUintError::InvalidDigit));
+	assert_eq!("+".parse::<BigUint>().unwrap_err().to_string(), "+".parse::<u64>().unwrap_err().to_string());
+	assert_eq!("-1".parse::<BigUint>(), Err(ParseBigUintError::InvalidDigit));
+	assert_eq!("12a".parse::<BigUint>(), Err(ParseBigUintError::InvalidDigit));
+	// same messages as the std integer types
+	assert_eq!("x".parse::<BigUint>().unwrap_err().to_string(), "x".parse::<u32>().unwrap_err().to_string());
+
+	assert_eq!(format!("{:>6}", BigUint::from(42)), "    42");
+	assert_eq!(format!("{:06}", BigUint::from(42)), "000042");
+	assert_eq!(format!("{:+}", BigUint::from(42)), "+42");
+	assert_eq!(format!("{:?}", BigUint::from(42)), "BigUint(42)");
+}
+
+fn big_uint_arithmetic() {
+	print_knowledage_point("big uint arithmetic");
+
+	// compare against u128, around the limb (2^32) and decimal chunk (10^9) boundaries
+	let lhs: [u128; 12] = [
+		0, 1, 7, 999_999_999, 1_000_000_000, u32::MAX as u128, 1 << 32,
+		u64::MAX as u128, 1 << 64, (1 << 96) - 1, 12_345_678_901_234_567_890_123_456_789, u128::MAX,
+	];
+	let rhs: [u64; 9] = [1, 2, 10, 999_999_999, 1_000_000_000, u32::MAX as u64, 1 << 32, 12_345_678_901_234_567, u64::MAX];
+	for (a, b) in lhs.iter().flat_map(|&a| rhs.iter().map(move |&b| (a, b as u128))) {
+		let (big_a, big_b) = (a.to_string().parse::<BigUint>().unwrap(), BigUint::from(b as u64));
+
+		if let Some(sum) = a.checked_add(b) {
+			assert_eq!((&big_a + &big_b).to_string(), sum.to_string());
+		}
+		if let Some(product) = a.checked_mul(b) {
+			assert_eq!((&big_a * &big_b).to_string(), product.to_string());
+		}
+		assert_eq!((&big_a / &big_b).to_string(), (a / b).to_string());
+		assert_eq!((&big_a % &big_b).to_string(), (a % b).to_string());
+		assert_eq!(big_a.cmp(&big_b), a.cmp(&b));
+		if a >= b {
+			assert_eq!((&big_a - &big_b).to_string(), (a - b).to_string());
+		} else {
+			assert_eq!(big_a.checked_sub(&big_b), None);
+		}
+	}
+
+	// multi-limb divisors, checked with the identity a = q * b + r, r < b
+	let a = factorial(60);
+	let b: BigUint = "12345678901234567890123".parse().unwrap();
+	let (q, r) = a.div_rem(&b);
+	assert!(r < b);
+	assert_eq!(&(&q * &b) + &r, a);
+	let same = a.clone();
+	assert_eq!(&a / &same, BigUint::from(1));
+	assert_eq!(&a % &same, BigUint::zero());
+	assert_eq!(&b / &a, BigUint::zero());
+}
+
+fn big_fibonacci_and_factorial() {
+	print_knowledage_point("big fibonacci and factorial");
+
+	// `Fibonacci` in 09_trait.rs overflows u32 after its 47th term
+	let f100 = big_fibonacci().nth(99).unwrap();
+	println!("The 100th Fibonacci number is {}", f100);
+	assert_eq!(f100.to_string(), "354224848179261915075");
+
+	for (i, f) in big_fibonacci().skip(45).take(4).enumerate() {
+		println!("> F({}) = {}", i + 46, f);
+	}
+
+	println!("30! = {}", factorial(30));
+	assert_eq!(factorial(30).to_string(), "265252859812191058636308480000000");
+	assert_eq!(factorial(0), BigUint::from(1));
+
+	let f1000 = factorial(1000).to_string();
+	assert_eq!(f1000.len(), 2568);
+	assert_eq!(f1000.chars().map(|c| c.to_digit(10).unwrap()).sum::<u32>(), 10539);
+	assert_eq!(f1000.parse::<BigUint>().unwrap(), factorial(1000));
+}
+
+fn main() {
+	big_uint_conversion();
+	big_uint_arithmetic();
+	big_fibonacci_and_factorial();
+}
@@ -0,0 +1,572 @@
+fn print_knowledage_point(s: &str) {
+	println!("~~~~~~~{}~~~~~~~", s);
+}
+
+/*
+05_flow_control.rs only declares the color models of `Color` and 01_base_output.rs
+has its own `Color { red, green, blue }`. Here both become one module:
+every model converts to every other one through RGB with f64 components,
+so HSV -> HSL -> CMYK -> HSV only loses the float rounding, `Rgb` (u8) is the only lossy model.
+*/
+mod color {
+	use std::fmt;
+	use std::str::FromStr;
+
+	// The hub of all conversions, each component in [0, 1]
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	pub struct Rgbf {
+		pub red: f64,
+		pub green: f64,
+		pub blue: f64,
+	}
+
+	// The familiar 0..=255 model, `{}` prints `rgb(128, 255, 90)`, `{:#}` prints `#80FF5A`
+	#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+	pub struct Rgb {
+		pub red: u8,
+		pub green: u8,
+		pub blue: u8,
+	}
+
+	// hue in degrees [0, 360), the others in [0, 1]
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	pub struct Hsv {
+		pub hue: f64,
+		pub saturation: f64,
+		pub value: f64,
+	}
+
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	pub struct Hsl {
+		pub hue: f64,
+		pub saturation: f64,
+		pub lightness: f64,
+	}
+
+	// the subtractive models, each component in [0, 1]
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	pub struct Cmy {
+		pub cyan: f64,
+		pub magenta: f64,
+		pub yellow: f64,
+	}
+
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	pub struct Cmyk {
+		pub cyan: f64,
+		pub magenta: f64,
+		pub yellow: f64,
+		pub key: f64,
+	}
+
+	// The enum of 05_flow_control.rs, the models now carry their own types
+	#[derive(Debug, Clone, Copy, PartialEq)]
+	pub enum Color {
+		Red,
+		Blue,
+		Green,
+		Rgb(Rgb),
+		Hsv(Hsv),
+		Hsl(Hsl),
+		Cmy(Cmy),
+		Cmyk(Cmyk),
+	}
+
+	pub trait ColorModel: Copy {
+		fn to_rgbf(self) -> Rgbf;
+		fn from_rgbf(rgb: Rgbf) -> Self;
+
+		fn convert<T: ColorModel>(self) -> T {
+			T::from_rgbf(self.to_rgbf())
+		}
+
+		// linear interpolation in RGB: 0.0 gives self, 1.0 gives other
+		fn blend<T: ColorModel>(self, other: T, t: f64) -> Self {
+			let (a, b) = (self.to_rgbf(), other.to_rgbf());
+			let mix = |x: f64, y: f64| x + (y - x) * t;
+			Self::from_rgbf(Rgbf {
+				red: mix(a.red, b.red),
+				green: mix(a.green, b.green),
+				blue: mix(a.blue, b.blue),
+			})
+		}
+
+		// WCAG relative luminance: 0.0 for black, 1.0 for white
+		fn relative_luminance(self) -> f64 {
+			fn linear(c: f64) -> f64 {
+				if c <= 0.040_45 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+			}
+
+			let c = self.to_rgbf();
+			0.2126 * linear(c.red) + 0.7152 * linear(c.green) + 0.0722 * linear(c.blue)
+		}
+
+		// WCAG contrast ratio, from 1 (same luminance) to 21 (black on white)
+		fn contrast_ratio<T: ColorModel>(self, other: T) -> f64 {
+			let (a, b) = (self.relative_luminance(), other.relative_luminance());
+			(a.max(b) + 0.05) / (a.min(b) + 0.05)
+		}
+	}
+
+	impl ColorModel for Rgbf {
+		fn to_rgbf(self) -> Rgbf {
+			self
+		}
+
+		fn from_rgbf(rgb: Rgbf) -> Self {
+			rgb
+		}
+	}
+
+	impl ColorModel for Rgb {
+		fn to_rgbf(self) -> Rgbf {
+			Rgbf {
+				red: self.red as f64 / 255.0,
+				green: self.green as f64 / 255.0,
+				blue: self.blue as f64 / 255.0,
+			}
+		}
+
+		fn from_rgbf(rgb: Rgbf) -> Self {
+			let byte = |c: f64| (c * 255.0).round().clamp(0.0, 255.0) as u8;
+			Rgb { red: byte(rgb.red), green: byte(rgb.green), blue: byte(rgb.blue) }
+		}
+	}
+
+	// (hue, max, min) of an RGB color, shared by HSV and HSL
+	fn hue_max_min(c: Rgbf) -> (f64, f64, f64) {
+		let max = c.red.max(c.green).max(c.blue);
+		let min = c.red.min(c.green).min(c.blue);
+		let delta = max - min;
+
+		// grays have no hue, 0 by convention
+		let hue = if delta == 0.0 {
+			0.0
+		} else if max == c.red {
+			60.0 * ((c.green - c.blue) / delta).rem_euclid(6.0)
+		} else if max == c.green {
+			60.0 * ((c.blue - c.red) / delta + 2.0)
+		} else {
+			60.0 * ((c.red - c.green) / delta + 4.0)
+		};
+
+		(hue, max, min)
+	}
+
+	// back from hue, chroma and the amount m added to every component
+	fn from_hue_chroma(hue: f64, chroma: f64, m: f64) -> Rgbf {
+		let h = hue.rem_euclid(360.0) / 60.0;
+		let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+		let (r, g, b) = match h as u32 {
+			0 => (chroma, x, 0.0),
+			1 => (x, chroma, 0.0),
+			2 => (0.0, chroma, x),
+			3 => (0.0, x, chroma),
+			4 => (x, 0.0, chroma),
+			_ => (chroma, 0.0, x),
+		};
+
+		Rgbf { red: r + m, green: g + m, blue: b + m }
+	}
+
+	impl ColorModel for Hsv {
+		fn to_rgbf(self) -> Rgbf {
+			let chroma = self.value * self.saturation;
+			from_hue_chroma(self.hue, chroma, self.value - chroma)
+		}
+
+		fn from_rgbf(rgb: Rgbf) -> Self {
+			let (hue, max, min) = hue_max_min(rgb);
+			let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+			Hsv { hue, saturation, value: max }
+		}
+	}
+
+	impl ColorModel for Hsl {
+		fn to_rgbf(self) -> Rgbf {
+			let chroma = (1.0 - (2.0 * self.lightness - 1.0).abs()) * self.saturation;
+			from_hue_chroma(self.hue, chroma, self.lightness - chroma / 2.0)
+		}
+
+		fn from_rgbf(rgb: Rgbf) -> Self {
+			let (hue, max, min) = hue_max_min(rgb);
+			let lightness = (max + min) / 2.0;
+			// black and white have no saturation
+			let saturation = if max == min {
+				0.0
+			} else {
+				(max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+			};
+			Hsl { hue, saturation, lightness }
+		}
+	}
+
+	impl ColorModel for Cmy {
+		fn to_rgbf(self) -> Rgbf {
+			Rgbf { red: 1.0 - self.cyan, green: 1.0 - self.magenta, blue: 1.0 - self.yellow }
+		}
+
+		fn from_rgbf(rgb: Rgbf) -> Self {
+			Cmy { cyan: 1.0 - rgb.red, magenta: 1.0 - rgb.green, yellow: 1.0 - rgb.blue }
+		}
+	}
+
+	impl ColorModel for Cmyk {
+		fn to_rgbf(self) -> Rgbf {
+			let white = 1.0 - self.key;
+			Rgbf {
+				red: (1.0 - self.cyan) * white,
+				green: (1.0 - self.magenta) * white,
+				blue: (1.0 - self.yellow) * white,
+			}
+		}
+
+		fn from_rgbf(rgb: Rgbf) -> Self {
+			let key = 1.0 - rgb.red.max(rgb.green).max(rgb.blue);
+			// pure black: the other inks don't matter
+			if key == 1.0 {
+				return Cmyk { cyan: 0.0, magenta: 0.0, yellow: 0.0, key };
+			}
+			let white = 1.0 - key;
+			Cmyk {
+				cyan: (white - rgb.red) / white,
+				magenta: (white - rgb.green) / white,
+				yellow: (white - rgb.blue) / white,
+				key,
+			}
+		}
+	}
+
+	impl ColorModel for Color {
+		fn to_rgbf(self) -> Rgbf {
+			match self {
+				// the CSS named colors
+				Color::Red => Rgb { red: 255, green: 0, blue: 0 }.to_rgbf(),
+				Color::Green => Rgb { red: 0, green: 128, blue: 0 }.to_rgbf(),
+				Color::Blue => Rgb { red: 0, green: 0, blue: 255 }.to_rgbf(),
+				Color::Rgb(c) => c.to_rgbf(),
+				Color::Hsv(c) => c.to_rgbf(),
+				Color::Hsl(c) => c.to_rgbf(),
+				Color::Cmy(c) => c.to_rgbf(),
+				Color::Cmyk(c) => c.to_rgbf(),
+			}
+		}
+
+		fn from_rgbf(rgb: Rgbf) -> Self {
+			Color::Rgb(Rgb::from_rgbf(rgb))
+		}
+	}
+
+	// `From` between every pair of models, each pair once in both directions
+	macro_rules! impl_conversions {
+		($first: ident $(, $rest: ident)*) => {
+			$(
+				impl From<$first> for $rest {
+					fn from(c: $first) -> Self {
+						c.convert()
+					}
+				}
+
+				impl From<$rest> for $first {
+					fn from(c: $rest) -> Self {
+						c.convert()
+					}
+				}
+			)*
+			impl_conversions!($($rest),*);
+		};
+		() => {};
+	}
+
+	impl_conversions!(Rgb, Hsv, Hsl, Cmy, Cmyk);
+
+	impl fmt::Display for Rgb {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			if f.alternate() {
+				write!(f, "#{:02X}{:02X}{:02X}", self.red, self.green, self.blue)
+			} else {
+				write!(f, "rgb({}, {}, {})", self.red, self.green, self.blue)
+			}
+		}
+	}
+
+	// `{:.1}` gives one decimal to every number
+	impl fmt::Display for Hsl {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			let p = f.precision().unwrap_or(0);
+			write!(f, "hsl({:.*}, {:.*}%, {:.*}%)",
+				p, self.hue, p, self.saturation * 100.0, p, self.lightness * 100.0)
+		}
+	}
+
+	impl fmt::Display for Hsv {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			let p = f.precision().unwrap_or(0);
+			write!(f, "hsv({:.*}, {:.*}%, {:.*}%)",
+				p, self.hue, p, self.saturation * 100.0, p, self.value * 100.0)
+		}
+	}
+
+	impl fmt::Display for Cmy {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			let p = f.precision().unwrap_or(0);
+			write!(f, "cmy({:.*}%, {:.*}%, {:.*}%)",
+				p, self.cyan * 100.0, p, self.magenta * 100.0, p, self.yellow * 100.0)
+		}
+	}
+
+	impl fmt::Display for Cmyk {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			let p = f.precision().unwrap_or(0);
+			write!(f, "cmyk({:.*}%, {:.*}%, {:.*}%, {:.*}%)",
+				p, self.cyan * 100.0, p, self.magenta * 100.0, p, self.yellow * 100.0, p, self.key * 100.0)
+		}
+	}
+
+	// the flags are passed on to the model
+	impl fmt::Display for Color {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match self {
+				Color::Red => write!(f, "red"),
+				Color::Green => write!(f, "green"),
+				Color::Blue => write!(f, "blue"),
+				Color::Rgb(c) => fmt::Display::fmt(c, f),
+				Color::Hsv(c) => fmt::Display::fmt(c, f),
+				Color::Hsl(c) => fmt::Display::fmt(c, f),
+				Color::Cmy(c) => fmt::Display::fmt(c, f),
+				Color::Cmyk(c) => fmt::Display::fmt(c, f),
+			}
+		}
+	}
+
+	#[derive(Debug, PartialEq)]
+	pub enum ParseColorError {
+		// not a name, `#...`, `rgb(...)` or `hsl(...)`
+		UnknownNotation,
+		InvalidHex,
+		WrongArgCount,
+		InvalidNumber,
+		OutOfRange,
+	}
+
+	impl fmt::Display for ParseColorError {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			let msg = match self {
+				ParseColorError::UnknownNotation => "unknown color notation",
+				ParseColorError::InvalidHex => "invalid hex color",
+				ParseColorError::WrongArgCount => "wrong number of color components",
+				ParseColorError::InvalidNumber => "invalid color component",
+				ParseColorError::OutOfRange => "color component out of range",
+			};
+			write!(f, "{}", msg)
+		}
+	}
+
+	// the comma separated arguments of `name(...)`
+	fn arguments<'a>(s: &'a str, name: &str) -> Option<Vec<&'a str>> {
+		let inner = s.strip_prefix(name)?.trim_start().strip_prefix('(')?.strip_suffix(')')?;
+		Some(inner.split(',').map(str::trim).collect())
+	}
+
+	fn number(s: &str, max: f64) -> Result<f64, ParseColorError> {
+		let n: f64 = s.parse().map_err(|_| ParseColorError::InvalidNumber)?;
+		if (0.0..=max).contains(&n) { Ok(n) } else { Err(ParseColorError::OutOfRange) }
+	}
+
+	// `25%` or `25`, both meaning 0.25
+	fn percent(s: &str) -> Result<f64, ParseColorError> {
+		Ok(number(s.strip_suffix('%').unwrap_or(s).trim_end(), 100.0)? / 100.0)
+	}
+
+	// `#80FF5A`, `#8F5`, `rgb(128, 255, 90)`, `hsl(120, 100%, 25%)`, `red`...
+	impl FromStr for Color {
+		type Err = ParseColorError;
+
+		fn from_str(s: &str) -> Result<Self, Self::Err> {
+			let s = s.trim();
+			let lower = s.to_ascii_lowercase();
+
+			if let Some(hex) = s.strip_prefix('#') {
+				if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+					return Err(ParseColorError::InvalidHex);
+				}
+				let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| ParseColorError::InvalidHex);
+				let (red, green, blue) = match hex.len() {
+					6 => (channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?),
+					// `#8F5` is `#88FF55`
+					3 => (channel(&hex[0..1])? * 17, channel(&hex[1..2])? * 17, channel(&hex[2..3])? * 17),
+					_ => return Err(ParseColorError::InvalidHex),
+				};
+				return Ok(Color::Rgb(Rgb { red, green, blue }));
+			}
+
+			if let Some(args) = arguments(&lower, "rgb") {
+				if let [r, g, b] = args.as_slice() {
+					let byte = |s: &str| s.parse::<u8>().map_err(|e| match e.kind() {
+						std::num::IntErrorKind::PosOverflow => ParseColorError::OutOfRange,
+						_ => ParseColorError::InvalidNumber,
+					});
+					return Ok(Color::Rgb(Rgb { red: byte(r)?, green: byte(g)?, blue: byte(b)? }));
+				}
+				return Err(ParseColorError::WrongArgCount);
+			}
+
+			if let Some(args) = arguments(&lower, "hsl") {
+				if let [h, s, l] = args.as_slice() {
+					let hue = number(h, 360.0)? % 360.0;
+					return Ok(Color::Hsl(Hsl { hue, saturation: percent(s)?, lightness: percent(l)? }));
+				}
+				return Err(ParseColorError::WrongArgCount);
+			}
+
+			match lower.as_str() {
+				"red" => Ok(Color::Red),
+				"green" => Ok(Color::Green),
+				"blue" => Ok(Color::Blue),
+				_ => Err(ParseColorError::UnknownNotation),
+			}
+		}
+	}
+}
+
+use color::{Cmy, Cmyk, Color, ColorModel, Hsl, Hsv, ParseColorError, Rgb, Rgbf};
+
+fn close(a: Rgbf, b: Rgbf) -> bool {
+	(a.red - b.red).abs() < 1e-9 && (a.green - b.green).abs() < 1e-9 && (a.blue - b.blue).abs() < 1e-9
+}
+
+fn color_match() {
+	print_knowledage_point("color match");
+
+	for color in [
+		Color::Red,
+		Color::Rgb(Rgb { red: 122, green: 17, blue: 40 }),
+		Color::Hsv(Hsv { hue: 210.0, saturation: 0.5, value: 0.4 }),
+		Color::Hsl(Hsl { hue: 120.0, saturation: 1.0, lightness: 0.25 }),
+		Color::Cmy(Cmy { cyan: 0.1, magenta: 0.8, yellow: 0.3 }),
+		Color::Cmyk(Cmyk { cyan: 0.0, magenta: 0.5, yellow: 1.0, key: 0.2 }),
+	].iter() {
+		// every model prints itself, and its RGB value
+		let rgb: Rgb = color.convert();
+		println!("{:<28} is {} {:#}", color.to_string(), rgb, rgb);
+
+		match color {
+			Color::Red | Color::Green | Color::Blue => println!("a named color"),
+			Color::Rgb(Rgb { red, green, blue }) =>
+				println!("Red: {}, green: {}, and blue: {}!", red, green, blue),
+			Color::Hsv(Hsv { hue, saturation, value }) =>
+				println!("Hue: {}, saturation: {}, value: {}!", hue, saturation, value),
+			Color::Hsl(Hsl { hue, saturation, lightness }) =>
+				println!("Hue: {}, saturation: {}, lightness: {}!", hue, saturation, lightness),
+			Color::Cmy(Cmy { cyan, magenta, yellow }) =>
+				println!("Cyan: {}, magenta: {}, yellow: {}!", cyan, magenta, yellow),
+			Color::Cmyk(Cmyk { cyan, magenta, yellow, key }) =>
+				println!("Cyan: {}, magenta: {}, yellow: {}, key (black): {}!", cyan, magenta, yellow, key),
+		}
+	}
+}
+
+fn color_conversions() {
+	print_knowledage_point("color conversions");
+
+	let olive = Rgb { red: 128, green: 128, blue: 0 };
+	let hsv = Hsv::from(olive);
+	let hsl = Hsl::from(olive);
+	let cmyk = Cmyk::from(olive);
+	println!("{} = {:.1} = {:.1} = {:.1} = {:.1}", olive, hsv, hsl, Cmy::from(olive), cmyk);
+
+	assert_eq!(hsv.hue, 60.0);
+	assert_eq!(hsv.saturation, 1.0);
+	assert_eq!(hsl.lightness, olive.to_rgbf().red / 2.0);
+	assert_eq!((cmyk.cyan, cmyk.magenta, cmyk.yellow), (0.0, 0.0, 1.0));
+	assert_eq!(Rgb::from(Hsl { hue: 120.0, saturation: 1.0, lightness: 0.25 }), Rgb { red: 0, green: 128, blue: 0 });
+	assert_eq!(Rgb::from(Cmyk { cyan: 0.0, magenta: 0.0, yellow: 0.0, key: 1.0 }), Rgb { red: 0, green: 0, blue: 0 });
+	assert_eq!(Cmyk::from(Rgb { red: 0, green: 0, blue: 0 }).key, 1.0);
+	// grays have no hue and no saturation
+	let gray = Hsv::from(Rgb { red: 90, green: 90, blue: 90 });
+	assert_eq!((gray.hue, gray.saturation), (0.0, 0.0));
+
+	// the 8-bit colors survive every model: all the mixes of the edges, the middle and a few others
+	let channels = [0, 1, 17, 64, 127, 128, 129, 200, 254, 255];
+	let mut grid = Vec::new();
+	for &red in channels.iter() {
+		for &green in channels.iter() {
+			for &blue in channels.iter() {
+				grid.push(Rgb { red, green, blue });
+			}
+		}
+	}
+	for rgb in grid {
+		assert_eq!(Rgb::from(Hsv::from(rgb)), rgb);
+		assert_eq!(Rgb::from(Hsl::from(rgb)), rgb);
+		assert_eq!(Rgb::from(Cmy::from(rgb)), rgb);
+		assert_eq!(Rgb::from(Cmyk::from(rgb)), rgb);
+
+		// and the float models don't go through u8 between each other
+		let hsv = Hsv::from(rgb);
+		let around = Hsv::from(Cmyk::from(Cmy::from(Hsl::from(hsv))));
+		assert!(close(around.to_rgbf(), hsv.to_rgbf()));
+	}
+}
+
+fn color_parse() {
+	print_knowledage_point("color parse");
+
+	let lime = Rgb { red: 128, green: 255, blue: 90 };
+	assert_eq!("#80FF5A".parse(), Ok(Color::Rgb(lime)));
+	assert_eq!("#80ff5a".parse(), Ok(Color::Rgb(lime)));
+	assert_eq!("#8F5".parse(), Ok(Color::Rgb(Rgb { red: 0x88, green: 0xFF, blue: 0x55 })));
+	assert_eq!("rgb(128, 255, 90)".parse(), Ok(Color::Rgb(lime)));
+	assert_eq!(" RGB( 128,255 ,90 ) ".parse(), Ok(Color::Rgb(lime)));
+	assert_eq!("hsl(120, 100%, 25%)".parse(), Ok(Color::Hsl(Hsl { hue: 120.0, saturation: 1.0, lightness: 0.25 })));
+	assert_eq!("Red".parse(), Ok(Color::Red));
+
+	// the Display output parses back
+	assert_eq!(lime.to_string().parse(), Ok(Color::Rgb(lime)));
+	assert_eq!(format!("{:#}", lime).parse(), Ok(Color::Rgb(lime)));
+	let hsl = Hsl { hue: 200.0, saturation: 0.5, lightness: 0.75 };
+	assert_eq!(hsl.to_string().parse(), Ok(Color::Hsl(hsl)));
+
+	assert_eq!("#80FF5".parse::<Color>(), Err(ParseColorError::InvalidHex));
+	assert_eq!("#80FG5A".parse::<Color>(), Err(ParseColorError::InvalidHex));
+	assert_eq!("rgb(1, 2)".parse::<Color>(), Err(ParseColorError::WrongArgCount));
+	assert_eq!("rgb(1, 2, 256)".parse::<Color>(), Err(ParseColorError::OutOfRange));
+	assert_eq!("rgb(1, x, 3)".parse::<Color>(), Err(ParseColorError::InvalidNumber));
+	assert_eq!("hsl(120, 101%, 25%)".parse::<Color>(), Err(ParseColorError::OutOfRange));
+	assert_eq!("purple".parse::<Color>(), Err(ParseColorError::UnknownNotation));
+
+	match "rgb(300, 0, 0)".parse::<Color>() {
+		Ok(c) => println!("parsed {}", c),
+		Err(e) => println!("Error: {}", e),
+	}
+}
+
+fn color_blend_contrast() {
+	print_knowledage_point("color blend and contrast");
+
+	let black = Rgb { red: 0, green: 0, blue: 0 };
+	let white = Rgb { red: 255, green: 255, blue: 255 };
+
+	let gray = black.blend(white, 0.5);
+	println!("black + white = {:#}", gray);
+	assert_eq!(gray, Rgb { red: 128, green: 128, blue: 128 });
+	assert_eq!(black.blend(Color::Red, 0.0), black);
+	assert_eq!(black.blend(Color::Red, 1.0), Rgb { red: 255, green: 0, blue: 0 });
+	// the result keeps the model of self
+	let purple = Hsl::from(Rgb { red: 255, green: 0, blue: 0 }).blend(Color::Blue, 0.5);
+	assert_eq!(Rgb::from(purple), Rgb { red: 128, green: 0, blue: 128 });
+
+	assert_eq!(black.contrast_ratio(white), 21.0);
+	assert_eq!(white.contrast_ratio(black), 21.0);
+	assert_eq!(Color::Red.contrast_ratio(Color::Red), 1.0);
+	let ratio = Color::Blue.contrast_ratio(white);
+	println!("blue on white: {:.2}:1", ratio);
+	assert!((ratio - 8.59).abs() < 0.01);
+}
+
+fn main() {
+	color_match();
+	color_conversions();
+	color_parse();
+	color_blend_contrast();
+}
@@ -0,0 +1,420 @@
+fn print_knowledage_point(s: &str) {
+	println!("~~~~~~~{}~~~~~~~", s);
+}
+
+use std::fmt::{self, Alignment, Display};
+
+/*
+A table of any `Display` cells. The flags of the formatter apply to every cell:
+`{:.2}` is the precision of each cell, `{:>8}` right aligns them in columns of at least 8 chars,
+`{:*^8}` pads with '*' instead of ' '. A column alignment or precision set on the table wins over the flag.
+As for `str`, the precision cuts text cells: mixed tables set it per column.
+Widths are counted in chars, so wide (CJK) chars take one column here.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+	Ascii,
+	Unicode,
+	Markdown,
+}
+
+// the chars of one horizontal line: left end, fill, between columns, right end
+struct Line(char, char, char, char);
+
+struct Border {
+	top: Line,
+	header: Line,
+	bottom: Line,
+	vertical: char,
+	ellipsis: char,
+}
+
+const ASCII: Border = Border {
+	top: Line('+', '-', '+', '+'),
+	header: Line('+', '=', '+', '+'),
+	bottom: Line('+', '-', '+', '+'),
+	vertical: '|',
+	ellipsis: '~',
+};
+
+const UNICODE: Border = Border {
+	top: Line('┌', '─', '┬', '┐'),
+	header: Line('├', '─', '┼', '┤'),
+	bottom: Line('└', '─', '┴', '┘'),
+	vertical: '│',
+	ellipsis: '…',
+};
+
+struct Table<'a> {
+	headers: Vec<String>,
+	rows: Vec<Vec<Box<dyn Display + 'a>>>,
+	style: Style,
+	// per column, None falls back to the formatter (or left)
+	alignments: Vec<Option<Alignment>>,
+	precisions: Vec<Option<usize>>,
+	max_widths: Vec<Option<usize>>,
+	max_width: Option<usize>,
+}
+
+impl<'a> Table<'a> {
+	fn new<I, S>(headers: I) -> Self
+		where I: IntoIterator<Item = S>, S: Into<String> {
+
+		Table {
+			headers: headers.into_iter().map(Into::into).collect(),
+			rows: Vec::new(),
+			style: Style::Ascii,
+			alignments: Vec::new(),
+			precisions: Vec::new(),
+			max_widths: Vec::new(),
+			max_width: None,
+		}
+	}
+
+	// `row([&"x" as &dyn Display, &1.5])` mixes the types of a row
+	fn row<I, D>(mut self, cells: I) -> Self
+		where I: IntoIterator<Item = D>, D: Display + 'a {
+
+		self.rows.push(cells.into_iter().map(|c| Box::new(c) as Box<dyn Display + 'a>).collect());
+		self
+	}
+
+	fn rows<R, I, D>(self, rows: R) -> Self
+		where R: IntoIterator<Item = I>, I: IntoIterator<Item = D>, D: Display + 'a {
+
+		rows.into_iter().fold(self, Table::row)
+	}
+
+	fn style(mut self, style: Style) -> Self {
+		self.style = style;
+		self
+	}
+
+	fn align(mut self, column: usize, alignment: Alignment) -> Self {
+		set(&mut self.alignments, column, alignment);
+		self
+	}
+
+	fn precision(mut self, column: usize, precision: usize) -> Self {
+		set(&mut self.precisions, column, precision);
+		self
+	}
+	
+	// longer cells are cut and end with an ellipsis
+	fn column_max_width(mut self, column: usize, width: usize) -> Self {
+		set(&mut self.max_widths, column, width);
+		self
+	}
+
+	fn max_width(mut self, width: usize) -> Self {
+		self.max_width = Some(width);
+		self
+	}
+
+	fn columns(&self) -> usize {
+		self.rows.iter().map(Vec::len).chain(Some(self.headers.len())).max().unwrap_or(0)
+	}
+}
+
+fn set<T>(values: &mut Vec<Option<T>>, index: usize, value: T) {
+	if values.len() <= index {
+		values.resize_with(index + 1, || None);
+	}
+	values[index] = Some(value);
+}
+
+// Escapes the markdown `|` first, then cuts the cell to `max` chars,
+// so the `\` counts in the width and a `\|` is never split
+fn render_cell(s: &str, max: Option<usize>, ellipsis: char, markdown: bool) -> String {
+	let width = |c: char| if markdown && c == '|' { 2 } else { 1 };
+	let push = |cell: &mut String, c: char| {
+		if markdown && c == '|' {
+			cell.push('\\');
+		}
+		cell.push(c);
+	};
+
+	let mut cell = String::new();
+	match max {
+		Some(max) if s.chars().map(width).sum::<usize>() > max => {
+			let mut used = 0;
+			for c in s.chars() {
+				used += width(c);
+				if used > max.saturating_sub(1) {
+					break;
+				}
+				push(&mut cell, c);
+			}
+			if max > 0 {
+				cell.push(ellipsis);
+			}
+		}
+		_ => s.chars().for_each(|c| push(&mut cell, c)),
+	}
+	cell
+}
+
+fn write_line(f: &mut fmt::Formatter, line: &Line, widths: &[usize]) -> fmt::Result {
+	let Line(left, fill, cross, right) = *line;
+	write!(f, "{}", left)?;
+	for (i, width) in widths.iter().enumerate() {
+		if i > 0 {
+			write!(f, "{}", cross)?;
+		}
+		// one space of margin on both sides
+		for _ in 0..width + 2 {
+			write!(f, "{}", fill)?;
+		}
+	}
+	writeln!(f, "{}", right)
+}
+
+fn write_cell(f: &mut fmt::Formatter, cell: &str, width: usize, alignment: Alignment, fill: char) -> fmt::Result {
+	let padding = width - cell.chars().count();
+	let (before, after) = match alignment {
+		Alignment::Left => (0, padding),
+		Alignment::Right => (padding, 0),
+		Alignment::Center => (padding / 2, padding - padding / 2),
+	};
+
+	for _ in 0..before {
+		write!(f, "{}", fill)?;
+	}
+	write!(f, "{}", cell)?;
+	for _ in 0..after {
+		write!(f, "{}", fill)?;
+	}
+	Ok(())
+}
+
+fn write_row(f: &mut fmt::Formatter, cells: &[String], widths: &[usize],
+	alignments: &[Alignment], vertical: char) -> fmt::Result {
+
+	let fill = f.fill();
+	write!(f, "{}", vertical)?;
+	for (i, &width) in widths.iter().enumerate() {
+		write!(f, " ")?;
+		write_cell(f, &cells[i], width, alignments[i], fill)?;
+		write!(f, " {}", vertical)?;
+	}
+	writeln!(f)
+}
+
+impl Display for Table<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let columns = self.columns();
+		if columns == 0 {
+			return Ok(());
+		}
+		let ellipsis = match self.style {
+			Style::Ascii => ASCII.ellipsis,
+			_ => UNICODE.ellipsis,
+		};
+		let max_width = |i: usize| self.max_widths.get(i).copied().flatten().or(self.max_width);
+
+		// every cell rendered once, with the precision of its column or of the formatter
+		let precision = |i: usize| self.precisions.get(i).copied().flatten().or_else(|| f.precision());
+		let render = |i: usize, s: String| render_cell(&s, max_width(i), ellipsis, self.style == Style::Markdown);
+		let header: Vec<String> = (0..columns)
+			.map(|i| render(i, self.headers.get(i).cloned().unwrap_or_default()))
+			.collect();
+		let body: Vec<Vec<String>> = self.rows.iter().map(|row| {
+			(0..columns).map(|i| {
+				let s = match (row.get(i), precision(i)) {
+					(Some(cell), Some(p)) => format!("{:.*}", p, cell),
+					(Some(cell), None) => cell.to_string(),
+					(None, _) => String::new(),
+				};
+				render(i, s)
+			}).collect()
+		}).collect();
+
+		let min_width = f.width().unwrap_or(0);
+		let widths: Vec<usize> = (0..columns).map(|i| {
+			body.iter().map(|row| &row[i]).chain(Some(&header[i]))
+				.map(|s| s.chars().count())
+				.fold(min_width, usize::max)
+		}).collect();
+
+		let default = f.align().unwrap_or(Alignment::Left);
+		let alignments: Vec<Alignment> = (0..columns)
+			.map(|i| self.alignments.get(i).copied().flatten().unwrap_or(default))
+			.collect();
+
+		match self.style {
+			Style::Markdown => {
+				write_row(f, &header, &widths, &alignments, '|')?;
+				// the alignment markers: `:--`, `:-:`, `--:`, at least 3 chars as required
+				write!(f, "|")?;
+				for (&width, alignment) in widths.iter().zip(&alignments) {
+					let dashes = "-".repeat(width.max(3) - 1);
+					match alignment {
+						Alignment::Left => write!(f, " :{} |", dashes)?,
+						Alignment::Right => write!(f, " {}: |", dashes)?,
+						Alignment::Center => write!(f, " :{}: |", &dashes[1..])?,
+					}
+				}
+				writeln!(f)?;
+				for row in &body {
+					write_row(f, row, &widths, &alignments, '|')?;
+				}
+				Ok(())
+			}
+			_ => {
+				let border = if self.style == Style::Ascii { &ASCII } else { &UNICODE };
+				write_line(f, &border.top, &widths)?;
+				write_row(f, &header, &widths, &alignments, border.vertical)?;
+				write_line(f, &border.header, &widths)?;
+				for row in &body {
+					write_row(f, row, &widths, &alignments, border.vertical)?;
+				}
+				write_line(f, &border.bottom, &widths)
+			}
+		}
+	}
+}
+
+// `List` of 01_base_output.rs, without its own loop
+struct List(Vec<i32>);
+
+impl Display for List {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let table = Table::new(["index", "value"])
+			.rows(self.0.iter().enumerate().map(|(i, v)| [i as i32, *v]))
+			.align(1, Alignment::Right);
+		Display::fmt(&table, f)
+	}
+}
+
+fn table_styles() {
+	print_knowledage_point("table styles");
+
+	let table = Table::new(["name", "lat", "lon"])
+		.row([&"Dublin" as &dyn Display, &53.347778, &-6.259722])
+		.row([&"Oslo" as &dyn Display, &59.95, &10.75])
+		.row([&"Vancouver" as &dyn Display, &49.25]);
+
+	let ascii = table.to_string();
+	print!("{}", ascii);
+	assert_eq!(ascii, "\
++-----------+-----------+-----------+
+| name      | lat       | lon       |
++===========+===========+===========+
+| Dublin    | 53.347778 | -6.259722 |
+| Oslo      | 59.95     | 10.75     |
+| Vancouver | 49.25     |           |
++-----------+-----------+-----------+
+");
+
+	let table = table.style(Style::Unicode).precision(1, 1).precision(2, 1);
+	let unicode = table.to_string();
+	print!("{}", unicode);
+	assert_eq!(unicode, "\
+┌───────────┬──────┬──────┐
+│ name      │ lat  │ lon  │
+├───────────┼──────┼──────┤
+│ Dublin    │ 53.3 │ -6.3 │
+│ Oslo      │ 60.0 │ 10.8 │
+│ Vancouver │ 49.2 │      │
+└───────────┴──────┴──────┘
+");
+
+	let table = table.style(Style::Markdown).precision(1, 2).precision(2, 2);
+	let markdown = table.align(0, Alignment::Center).align(1, Alignment::Right).to_string();
+	print!("{}", markdown);
+	assert_eq!(markdown, "\
+|   name    |   lat | lon   |
+| :-------: | ----: | :---- |
+|  Dublin   | 53.35 | -6.26 |
+|   Oslo    | 59.95 | 10.75 |
+| Vancouver | 49.25 |       |
+");
+
+	// the precision of the formatter, for a table of numbers only
+	let numbers = Table::new(["x", "x²"]).rows((1..=2).map(|x| [x as f64 / 3.0, (x * x) as f64 / 9.0]));
+	assert_eq!(format!("{:>.3}", numbers), "\
++-------+-------+
+|     x |    x² |
++=======+=======+
+| 0.333 | 0.111 |
+| 0.667 | 0.444 |
++-------+-------+
+");
+	
+	// a `|` in markdown is escaped
+	let escaped = Table::new(["a|b"]).row(["x|y"]).style(Style::Markdown).to_string();
+	assert_eq!(escaped, "| a\\|b |\n| :--- |\n| x\\|y |\n");
+	// and the `\` counts in the max width, the cut doesn't split `\|`
+	let cut = Table::new(["a|b|c"]).row(["ab|cd"]).style(Style::Markdown).max_width(4).to_string();
+	assert_eq!(cut, "| a\\|… |\n| :--- |\n| ab…  |\n");
+	assert!(cut.lines().all(|line| line.chars().count() == 8));
+}
+
+fn table_flags() {
+	print_knowledage_point("table flags");
+
+	let list = List(vec![1, 2, 300]);
+	print!("{}", list);
+	assert_eq!(list.to_string(), "\
++-------+-------+
+| index | value |
++=======+=======+
+| 0     |     1 |
+| 1     |     2 |
+| 2     |   300 |
++-------+-------+
+");
+
+	// width and fill come from the formatter, the alignment of column 1 stays
+	let padded = format!("{:*^7}", list);
+	print!("{}", padded);
+	assert_eq!(padded, "\
++---------+---------+
+| *index* | **value |
++=========+=========+
+| ***0*** | ******1 |
+| ***1*** | ******2 |
+| ***2*** | ****300 |
++---------+---------+
+");
+}
+
+fn table_truncation() {
+	print_knowledage_point("table truncation");
+
+	let text = "The quick brown fox jumped over the lazy dog";
+	let table = Table::new(["id", "sentence"])
+		.row([&1 as &dyn Display, &text])
+		.row([&2 as &dyn Display, &"short"])
+		.column_max_width(1, 12);
+
+	print!("{}", table);
+	assert_eq!(table.to_string(), "\
++----+--------------+
+| id | sentence     |
++====+==============+
+| 1  | The quick b~ |
+| 2  | short        |
++----+--------------+
+");
+
+	// a global limit, the column one still wins for column 1
+	let table = table.max_width(1).style(Style::Unicode);
+	assert_eq!(table.to_string(), "\
+┌───┬──────────────┐
+│ … │ sentence     │
+├───┼──────────────┤
+│ 1 │ The quick b… │
+│ 2 │ short        │
+└───┴──────────────┘
+");
+
+	let empty = Table::new(Vec::<String>::new());
+	assert_eq!(empty.to_string(), "");
+}
+
+fn main() {
+	table_styles();
+	table_flags();
+	table_truncation();
+}
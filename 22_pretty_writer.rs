@@ -0,0 +1,369 @@
+fn print_knowledage_point(s: &str) {
+	println!("~~~~~~~{}~~~~~~~", s);
+}
+
+use std::fmt::{self, Display, Write};
+
+/*
+The `Display` impls of 01_base_output.rs and 02_custom_types.rs are flat `write!` calls,
+a nested value always ends on the same line. `PrettyWriter` offers the builders
+`Formatter::debug_struct` & co. offer to `Debug`: `{}` stays on one line,
+`{:#}` puts one field per line and indents the nested values, like `{:#?}` does.
+
+The indentation is tracked by `PadAdapter`: it writes the indent after every '\n'.
+A nested value is written into it with `{:#}`, so its own builders indent once more.
+
+As with `Debug`, the flags reach the nested values: `{:.2}` and `{:8}` apply to every field.
+On one line the value is written with the caller's `Formatter`, so all of them are kept.
+The `PadAdapter` needs a new `Formatter`, and stable Rust can only build one from a format string:
+width and precision are forwarded, fill, alignment, `+` and `0` are lost in `{:#}`.
+*/
+
+const INDENT: &str = "    ";
+
+// Writes INDENT at the start of every line going through it
+struct PadAdapter<'a> {
+	inner: &'a mut dyn Write,
+	on_newline: bool,
+}
+
+impl<'a> PadAdapter<'a> {
+	// the caller has just written a '\n'
+	fn new(inner: &'a mut dyn Write) -> Self {
+		PadAdapter { inner, on_newline: true }
+	}
+}
+
+impl Write for PadAdapter<'_> {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		for line in s.split_inclusive('\n') {
+			if self.on_newline {
+				self.inner.write_str(INDENT)?;
+			}
+			self.on_newline = line.ends_with('\n');
+			self.inner.write_str(line)?;
+		}
+		Ok(())
+	}
+}
+
+struct PrettyWriter<'a, 'b> {
+	f: &'a mut fmt::Formatter<'b>,
+}
+
+impl<'a, 'b> PrettyWriter<'a, 'b> {
+	fn new(f: &'a mut fmt::Formatter<'b>) -> Self {
+		PrettyWriter { f }
+	}
+
+	fn is_pretty(&self) -> bool {
+		self.f.alternate()
+	}
+
+	// `Name { a: 1, b: 2 }`
+	fn display_struct(&mut self, name: &str) -> DisplayStruct<'_, 'a, 'b> {
+		let result = self.f.write_str(name);
+		DisplayStruct { writer: self, result, has_fields: false }
+	}
+
+	// `Name(1, 2)`
+	fn display_tuple(&mut self, name: &str) -> DisplayTuple<'_, 'a, 'b> {
+		let result = self.f.write_str(name);
+		DisplayTuple { writer: self, result, fields: 0 }
+	}
+
+	// `[1, 2]`
+	fn display_list(&mut self) -> DisplayList<'_, 'a, 'b> {
+		let result = self.f.write_str("[");
+		DisplayList { writer: self, result, has_entries: false }
+	}
+
+	// one item of a struct, tuple or list: `{:#}` on its own indented line, or `{}` after `prefix`
+	fn item(&mut self, first: bool, open: &str, prefix: &str, name: Option<&str>, value: &dyn Display) -> fmt::Result {
+		if self.is_pretty() {
+			if first {
+				self.f.write_str(open.trim_end())?;
+				self.f.write_str("\n")?;
+			}
+			let (width, precision) = (self.f.width(), self.f.precision());
+			let mut pad = PadAdapter::new(self.f);
+			if let Some(name) = name {
+				write!(pad, "{}: ", name)?;
+			}
+			match (width, precision) {
+				(None, None) => write!(pad, "{:#}", value)?,
+				(Some(w), None) => write!(pad, "{:#w$}", value, w = w)?,
+				(None, Some(p)) => write!(pad, "{:#.p$}", value, p = p)?,
+				(Some(w), Some(p)) => write!(pad, "{:#w$.p$}", value, w = w, p = p)?,
+			}
+			pad.write_str(",\n")
+		} else {
+			self.f.write_str(if first { open } else { prefix })?;
+			if let Some(name) = name {
+				write!(self.f, "{}: ", name)?;
+			}
+			value.fmt(self.f)
+		}
+	}
+}
+
+struct DisplayStruct<'w, 'a, 'b> {
+	writer: &'w mut PrettyWriter<'a, 'b>,
+	result: fmt::Result,
+	has_fields: bool,
+}
+
+impl DisplayStruct<'_, '_, '_> {
+	fn field(&mut self, name: &str, value: &dyn Display) -> &mut Self {
+		let first = !self.has_fields;
+		self.result = self.result.and_then(|_| self.writer.item(first, " { ", ", ", Some(name), value));
+		self.has_fields = true;
+		self
+	}
+
+	fn finish(&mut self) -> fmt::Result {
+		self.result.and_then(|_| match (self.has_fields, self.writer.is_pretty()) {
+			(false, _) => Ok(()),
+			(true, true) => self.writer.f.write_str("}"),
+			(true, false) => self.writer.f.write_str(" }"),
+		})
+	}
+}
+
+struct DisplayTuple<'w, 'a, 'b> {
+	writer: &'w mut PrettyWriter<'a, 'b>,
+	result: fmt::Result,
+	fields: usize,
+}
+
+impl DisplayTuple<'_, '_, '_> {
+	fn field(&mut self, value: &dyn Display) -> &mut Self {
+		let first = self.fields == 0;
+		self.result = self.result.and_then(|_| self.writer.item(first, "(", ", ", None, value));
+		self.fields += 1;
+		self
+	}
+
+	fn finish(&mut self) -> fmt::Result {
+		self.result.and_then(|_| if self.fields > 0 { self.writer.f.write_str(")") } else { Ok(()) })
+	}
+}
+
+struct DisplayList<'w, 'a, 'b> {
+	writer: &'w mut PrettyWriter<'a, 'b>,
+	result: fmt::Result,
+	has_entries: bool,
+}
+
+impl DisplayList<'_, '_, '_> {
+	fn entry(&mut self, value: &dyn Display) -> &mut Self {
+		let first = !self.has_entries;
+		// `[` is already written
+		self.result = self.result.and_then(|_| self.writer.item(first, "", ", ", None, value));
+		self.has_entries = true;
+		self
+	}
+
+	fn entries<D: Display, I: IntoIterator<Item = D>>(&mut self, entries: I) -> &mut Self {
+		for entry in entries {
+			self.entry(&entry);
+		}
+		self
+	}
+
+	fn finish(&mut self) -> fmt::Result {
+		self.result.and_then(|_| self.writer.f.write_str("]"))
+	}
+}
+
+// The types of 02_custom_types.rs, now with a `Display`
+#[derive(Debug)]
+struct Point3D {
+	x: i64,
+	y: i64,
+	z: i64,
+}
+
+impl Display for Point3D {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		PrettyWriter::new(f).display_struct("Point3D")
+			.field("x", &self.x)
+			.field("y", &self.y)
+			.field("z", &self.z)
+			.finish()
+	}
+}
+
+#[derive(Debug)]
+enum WebEvent {
+	PageLoad,
+	PageUnload,
+	KeyPress(char),
+	Paste(String),
+	Click { x: i64, y: i64 },
+	Draw(Point3D),
+}
+
+impl Display for WebEvent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut w = PrettyWriter::new(f);
+		match self {
+			WebEvent::PageLoad => w.display_struct("PageLoad").finish(),
+			WebEvent::PageUnload => w.display_struct("PageUnload").finish(),
+			WebEvent::KeyPress(key) => w.display_tuple("KeyPress").field(&format_args!("'{}'", key)).finish(),
+			WebEvent::Paste(s) => w.display_tuple("Paste").field(&format_args!("\"{}\"", s)).finish(),
+			WebEvent::Click { x, y } => w.display_struct("Click").field("x", x).field("y", y).finish(),
+			WebEvent::Draw(p) => w.display_tuple("Draw").field(p).finish(),
+		}
+	}
+}
+
+// a deeper nesting: a struct holding a list of enums holding structs
+struct Session {
+	user: &'static str,
+	events: Vec<WebEvent>,
+}
+
+impl Display for Session {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		PrettyWriter::new(f).display_struct("Session")
+			.field("user", &self.user)
+			.field("events", &EventList(&self.events))
+			.finish()
+	}
+}
+
+struct EventList<'a>(&'a [WebEvent]);
+
+impl Display for EventList<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		PrettyWriter::new(f).display_list().entries(self.0).finish()
+	}
+}
+
+// a list of floats, to compare with the Debug of a Vec<f64>
+struct Samples<'a>(&'a [f64]);
+
+impl Display for Samples<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		PrettyWriter::new(f).display_list().entries(self.0).finish()
+	}
+}
+
+fn pretty_struct() {
+	print_knowledage_point("pretty struct");
+
+	let point = Point3D { x: 10, y: 20, z: -5 };
+	println!("{}", point);
+	println!("{:#}", point);
+
+	assert_eq!(point.to_string(), "Point3D { x: 10, y: 20, z: -5 }");
+	// the same layout as the derived Debug
+	assert_eq!(format!("{}", point), format!("{:?}", point));
+	assert_eq!(format!("{:#}", point), format!("{:#?}", point));
+}
+
+fn pretty_enum() {
+	print_knowledage_point("pretty enum");
+
+	let events = [
+		WebEvent::PageLoad,
+		WebEvent::KeyPress('x'),
+		WebEvent::Paste(String::from("my text")),
+		WebEvent::Click { x: 20, y: 80 },
+		WebEvent::Draw(Point3D { x: 10, y: 20, z: -5 }),
+		WebEvent::PageUnload,
+	];
+
+	for event in events.iter() {
+		println!("{}", event);
+		// Display and Debug only differ by the quotes of the strings
+		assert_eq!(format!("{}", event), format!("{:?}", event));
+		assert_eq!(format!("{:#}", event), format!("{:#?}", event));
+	}
+
+	println!("{:#}", events[4]);
+	assert_eq!(format!("{:#}", events[4]), "\
+Draw(
+    Point3D {
+        x: 10,
+        y: 20,
+        z: -5,
+    },
+)");
+}
+
+fn pretty_nested() {
+	print_knowledage_point("pretty nested");
+
+	let session = Session {
+		user: "ferris",
+		events: vec![WebEvent::Paste(String::from("two\nlines")), WebEvent::Draw(Point3D { x: 1, y: 2, z: 3 })],
+	};
+
+	println!("{}", session);
+	println!("{:#}", session);
+	assert_eq!(session.to_string(),
+		"Session { user: ferris, events: [Paste(\"two\nlines\"), Draw(Point3D { x: 1, y: 2, z: 3 })] }");
+	// even a multi-line string is indented with its field
+	assert_eq!(format!("{:#}", session), "\
+Session {
+    user: ferris,
+    events: [
+        Paste(
+            \"two
+            lines\",
+        ),
+        Draw(
+            Point3D {
+                x: 1,
+                y: 2,
+                z: 3,
+            },
+        ),
+    ],
+}");
+
+	let empty = Session { user: "nobody", events: vec![] };
+	assert_eq!(format!("{:#}", empty), "Session {\n    user: nobody,\n    events: [],\n}");
+}
+
+fn pretty_flags() {
+	print_knowledage_point("pretty flags");
+
+	let values = vec![1.5, -2.25, 0.4];
+	let samples = Samples(&values);
+	println!("{:.2}", samples);
+	println!("{:#8.3}", samples);
+
+	// width and precision reach the nested values, as with Debug
+	assert_eq!(format!("{:.2}", samples), "[1.50, -2.25, 0.40]");
+	assert_eq!(format!("{:.2}", samples), format!("{:.2?}", values));
+	assert_eq!(format!("{:#.2}", samples), format!("{:#.2?}", values));
+	assert_eq!(format!("{:#8.3}", samples), format!("{:#8.3?}", values));
+	let point = Point3D { x: 10, y: 20, z: -5 };
+	assert_eq!(format!("{:4}", point), "Point3D { x:   10, y:   20, z:   -5 }");
+	assert_eq!(format!("{:#4}", point), format!("{:#4?}", point));
+	assert_eq!(format!("{:#4}", WebEvent::Draw(point)), "\
+Draw(
+    Point3D {
+        x:   10,
+        y:   20,
+        z:   -5,
+    },
+)");
+
+	// on one line every flag is kept
+	assert_eq!(format!("{:+}", samples), format!("{:+?}", values));
+	assert_eq!(format!("{:<6}", samples), format!("{:<6?}", values));
+	// over several lines the sign and the alignment are lost
+	assert_eq!(format!("{:+#}", samples), format!("{:#?}", values));
+	assert_eq!(format!("{:<#6}", samples), format!("{:#6?}", values));
+}
+
+fn main() {
+	pretty_struct();
+	pretty_enum();
+	pretty_nested();
+	pretty_flags();
+}
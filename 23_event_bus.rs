@@ -0,0 +1,370 @@
+fn print_knowledage_point(s: &str) {
+	println!("~~~~~~~{}~~~~~~~", s);
+}
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+
+// The types of 02_custom_types.rs
+#[derive(Debug, Clone, PartialEq)]
+struct Point3D {
+	x: i64,
+	y: i64,
+	z: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum WebEvent {
+	PageLoad,
+	PageUnload,
+	KeyPress(char),
+	Paste(String),
+	Click { x: i64, y: i64 },
+	Draw(Point3D),
+}
+
+// The variant of a WebEvent without its data, what handlers subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventKind {
+	PageLoad,
+	PageUnload,
+	KeyPress,
+	Paste,
+	Click,
+	Draw,
+}
+
+impl WebEvent {
+	fn kind(&self) -> EventKind {
+		match self {
+			WebEvent::PageLoad => EventKind::PageLoad,
+			WebEvent::PageUnload => EventKind::PageUnload,
+			WebEvent::KeyPress(_) => EventKind::KeyPress,
+			WebEvent::Paste(_) => EventKind::Paste,
+			WebEvent::Click { .. } => EventKind::Click,
+			WebEvent::Draw(_) => EventKind::Draw,
+		}
+	}
+
+	// `WebEvent::inspect` of 02_custom_types.rs, now one handler among others
+	fn inspect(&self) {
+		match self {
+			WebEvent::PageLoad => println!("Page load"),
+			WebEvent::PageUnload => println!("Page unload"),
+			WebEvent::KeyPress(key) => println!("pressed '{}'", key),
+			WebEvent::Paste(s) => println!("pasted \"{}\"", s),
+			WebEvent::Click { x, y } => println!("clicked at x={}, y={}", x, y),
+			WebEvent::Draw(p) => println!("draw at x={}, y={}, z={}", p.x, p.y, p.z),
+		}
+	}
+}
+
+// What a handler returns: `Stop` cancels the handlers of lower priority
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Propagation {
+	Continue,
+	Stop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct HandlerId(u64);
+
+// `Send + Sync` so an `Arc<Dispatcher>` can be shared between threads
+type Handler = Arc<dyn Fn(&WebEvent) -> Propagation + Send + Sync>;
+
+struct Registration {
+	id: HandlerId,
+	kind: EventKind,
+	priority: i32,
+	handler: Handler,
+}
+
+// What happened to one dispatched event
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Dispatched {
+	handlers_run: usize,
+	cancelled: bool,
+}
+
+/*
+All the methods take `&self`, the state is behind locks.
+The handlers are called without any lock held: a handler may dispatch, subscribe or unsubscribe,
+a change is seen by the next event only.
+*/
+struct Dispatcher {
+	// sorted by priority, highest first, then by subscription order
+	handlers: RwLock<Vec<Registration>>,
+	history: Mutex<Vec<WebEvent>>,
+	next_id: AtomicU64,
+}
+
+impl Dispatcher {
+	fn new() -> Self {
+		Dispatcher {
+			handlers: RwLock::new(Vec::new()),
+			history: Mutex::new(Vec::new()),
+			next_id: AtomicU64::new(0),
+		}
+	}
+
+	// a higher priority runs first, handlers of the same priority run in subscription order
+	fn subscribe<F>(&self, kind: EventKind, priority: i32, handler: F) -> HandlerId
+		where F: Fn(&WebEvent) -> Propagation + Send + Sync + 'static {
+
+		let id = HandlerId(self.next_id.fetch_add(1, Ordering::Relaxed));
+		let mut handlers = self.handlers.write().unwrap();
+		// after every handler of the same or higher priority
+		let index = handlers.iter().position(|r| r.priority < priority).unwrap_or(handlers.len());
+		handlers.insert(index, Registration { id, kind, priority, handler: Arc::new(handler) });
+		id
+	}
+
+	fn unsubscribe(&self, id: HandlerId) -> bool {
+		let mut handlers = self.handlers.write().unwrap();
+		match handlers.iter().position(|r| r.id == id) {
+			Some(index) => {
+				handlers.remove(index);
+				true
+			}
+			None => false,
+		}
+	}
+
+	// runs the handlers of the event, then records it in the history
+	fn dispatch(&self, event: WebEvent) -> Dispatched {
+		let dispatched = self.run(&event);
+		self.history.lock().unwrap().push(event);
+		dispatched
+	}
+
+	fn run(&self, event: &WebEvent) -> Dispatched {
+		let kind = event.kind();
+		// a snapshot: the lock is released before the handlers are called
+		let handlers: Vec<Handler> = self.handlers.read().unwrap().iter()
+			.filter(|r| r.kind == kind)
+			.map(|r| Arc::clone(&r.handler))
+			.collect();
+
+		let mut handlers_run = 0;
+		for handler in handlers {
+			handlers_run += 1;
+			if handler(event) == Propagation::Stop {
+				return Dispatched { handlers_run, cancelled: true };
+			}
+		}
+		Dispatched { handlers_run, cancelled: false }
+	}
+
+	fn history(&self) -> Vec<WebEvent> {
+		self.history.lock().unwrap().clone()
+	}
+
+	fn clear_history(&self) -> Vec<WebEvent> {
+		std::mem::take(&mut *self.history.lock().unwrap())
+	}
+
+	// runs the recorded events again through the current handlers, the history doesn't grow
+	fn replay(&self) -> Vec<Dispatched> {
+		self.history().iter().map(|event| self.run(event)).collect()
+	}
+
+	// a thread dispatching everything sent on the channel,
+	// it ends when every Sender is dropped and returns the number of events
+	fn spawn_worker(self: &Arc<Self>) -> (Sender<WebEvent>, JoinHandle<usize>) {
+		let (tx, rx) = mpsc::channel();
+		let dispatcher = Arc::clone(self);
+		let worker = thread::spawn(move || {
+			let mut count = 0;
+			for event in rx {
+				dispatcher.dispatch(event);
+				count += 1;
+			}
+			count
+		});
+		(tx, worker)
+	}
+}
+
+fn sample_events() -> Vec<WebEvent> {
+	vec![
+		WebEvent::PageLoad,
+		WebEvent::KeyPress('x'),
+		WebEvent::Paste(String::from("my text")),
+		WebEvent::Click { x: 20, y: 80 },
+		WebEvent::Draw(Point3D { x: 10, y: 20, z: -5 }),
+		WebEvent::PageUnload,
+	]
+}
+
+fn dispatch_inspect() {
+	print_knowledage_point("dispatch inspect");
+
+	let dispatcher = Dispatcher::new();
+	for &kind in [EventKind::PageLoad, EventKind::PageUnload, EventKind::KeyPress,
+		EventKind::Paste, EventKind::Click, EventKind::Draw].iter() {
+
+		dispatcher.subscribe(kind, 0, |event| {
+			event.inspect();
+			Propagation::Continue
+		});
+	}
+
+	for event in sample_events() {
+		assert_eq!(dispatcher.dispatch(event), Dispatched { handlers_run: 1, cancelled: false });
+	}
+}
+
+fn dispatch_priority() {
+	print_knowledage_point("dispatch priority and cancellation");
+
+	let dispatcher = Dispatcher::new();
+	let calls = Arc::new(Mutex::new(Vec::new()));
+
+	let log = |name: &'static str, propagation: Propagation| {
+		let calls = Arc::clone(&calls);
+		move |_: &WebEvent| {
+			calls.lock().unwrap().push(name);
+			propagation
+		}
+	};
+
+	dispatcher.subscribe(EventKind::Click, 0, log("default", Propagation::Continue));
+	dispatcher.subscribe(EventKind::Click, 10, log("high", Propagation::Continue));
+	dispatcher.subscribe(EventKind::Click, 0, log("default 2", Propagation::Continue));
+	dispatcher.subscribe(EventKind::Click, -10, log("low", Propagation::Continue));
+	dispatcher.subscribe(EventKind::KeyPress, 100, log("key", Propagation::Continue));
+
+	let result = dispatcher.dispatch(WebEvent::Click { x: 1, y: 2 });
+	assert_eq!(result, Dispatched { handlers_run: 4, cancelled: false });
+	assert_eq!(*calls.lock().unwrap(), ["high", "default", "default 2", "low"]);
+
+	// a modal dialog swallows the clicks
+	calls.lock().unwrap().clear();
+	let modal = dispatcher.subscribe(EventKind::Click, 5, log("modal", Propagation::Stop));
+	let result = dispatcher.dispatch(WebEvent::Click { x: 1, y: 2 });
+	println!("with the modal: {:?}, calls {:?}", result, calls.lock().unwrap());
+	assert_eq!(result, Dispatched { handlers_run: 2, cancelled: true });
+	assert_eq!(*calls.lock().unwrap(), ["high", "modal"]);
+
+	calls.lock().unwrap().clear();
+	assert!(dispatcher.unsubscribe(modal));
+	assert!(!dispatcher.unsubscribe(modal));
+	assert_eq!(dispatcher.dispatch(WebEvent::Click { x: 1, y: 2 }).handlers_run, 4);
+
+	// no handler for this kind
+	assert_eq!(dispatcher.dispatch(WebEvent::PageLoad), Dispatched { handlers_run: 0, cancelled: false });
+}
+
+fn dispatch_history() {
+	print_knowledage_point("dispatch history and replay");
+
+	let dispatcher = Dispatcher::new();
+	let pasted = Arc::new(Mutex::new(String::new()));
+	let text = Arc::clone(&pasted);
+	dispatcher.subscribe(EventKind::Paste, 0, move |event| {
+		if let WebEvent::Paste(s) = event {
+			text.lock().unwrap().push_str(s);
+		}
+		Propagation::Continue
+	});
+
+	for event in sample_events() {
+		dispatcher.dispatch(event);
+	}
+	assert_eq!(dispatcher.history(), sample_events());
+	assert_eq!(*pasted.lock().unwrap(), "my text");
+
+	// a handler subscribed later sees the past events on replay
+	let clicks = Arc::new(AtomicUsize::new(0));
+	let counter = Arc::clone(&clicks);
+	dispatcher.subscribe(EventKind::Click, 0, move |_| {
+		counter.fetch_add(1, Ordering::SeqCst);
+		Propagation::Continue
+	});
+
+	let replayed = dispatcher.replay();
+	println!("replayed: {:?}", replayed);
+	assert_eq!(replayed.len(), sample_events().len());
+	assert_eq!(clicks.load(Ordering::SeqCst), 1);
+	assert_eq!(*pasted.lock().unwrap(), "my textmy text");
+	// replay doesn't record
+	assert_eq!(dispatcher.history().len(), sample_events().len());
+
+	assert_eq!(dispatcher.clear_history(), sample_events());
+	assert!(dispatcher.history().is_empty());
+}
+
+// a handler may use the dispatcher, no lock is held while it runs
+fn dispatch_reentrant() {
+	print_knowledage_point("dispatch reentrant");
+
+	let dispatcher = Arc::new(Dispatcher::new());
+	let inner = Arc::downgrade(&dispatcher);
+	// every key press also draws
+	dispatcher.subscribe(EventKind::KeyPress, 0, move |_| {
+		if let Some(dispatcher) = inner.upgrade() {
+			dispatcher.dispatch(WebEvent::Draw(Point3D { x: 0, y: 0, z: 0 }));
+		}
+		Propagation::Continue
+	});
+
+	dispatcher.dispatch(WebEvent::KeyPress('a'));
+	// the nested dispatch ends first
+	assert_eq!(dispatcher.history(), [WebEvent::Draw(Point3D { x: 0, y: 0, z: 0 }), WebEvent::KeyPress('a')]);
+}
+
+const NTHREADS: usize = 3;
+
+fn dispatch_threads() {
+	print_knowledage_point("dispatch threads");
+
+	let dispatcher = Arc::new(Dispatcher::new());
+	let keys = Arc::new(AtomicUsize::new(0));
+	let counter = Arc::clone(&keys);
+	dispatcher.subscribe(EventKind::KeyPress, 0, move |_| {
+		counter.fetch_add(1, Ordering::SeqCst);
+		Propagation::Continue
+	});
+
+	// producers send to the worker through the channel, as in 13_std_misc.rs
+	let (tx, worker) = dispatcher.spawn_worker();
+	let mut producers = Vec::new();
+	for id in 0..NTHREADS {
+		let thread_tx = tx.clone();
+		producers.push(thread::spawn(move || {
+			for c in "abc".chars() {
+				thread_tx.send(WebEvent::KeyPress(c)).unwrap();
+			}
+			thread_tx.send(WebEvent::Click { x: id as i64, y: 0 }).unwrap();
+		}));
+	}
+
+	// other threads may also dispatch directly through the Arc
+	let direct = {
+		let dispatcher = Arc::clone(&dispatcher);
+		thread::spawn(move || dispatcher.dispatch(WebEvent::KeyPress('z')))
+	};
+
+	for producer in producers {
+		producer.join().expect("oops! the producer thread panicked");
+	}
+	assert_eq!(direct.join().unwrap().handlers_run, 1);
+	// the worker stops once the last sender is gone
+	drop(tx);
+	let count = worker.join().unwrap();
+
+	println!("worker dispatched {} events, {} key presses in all", count, keys.load(Ordering::SeqCst));
+	assert_eq!(count, NTHREADS * 4);
+	assert_eq!(keys.load(Ordering::SeqCst), NTHREADS * 3 + 1);
+	assert_eq!(dispatcher.history().len(), NTHREADS * 4 + 1);
+}
+
+fn main() {
+	dispatch_inspect();
+	dispatch_priority();
+	dispatch_history();
+	dispatch_reentrant();
+	dispatch_threads();
+}
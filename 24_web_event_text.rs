@@ -0,0 +1,559 @@
+fn print_knowledage_point(s: &str) {
+	println!("~~~~~~~{}~~~~~~~", s);
+}
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+/*
+A line oriented text format for the WebEvent of 02_custom_types.rs, one event per line:
+
+	# a comment, blank lines are skipped too
+	pageload
+	keypress 'x'
+	paste "my text with \"quotes\"\n"
+	click x=20 y=80
+	draw x=10 y=20 z=-5
+	pageunload
+
+The fields of `click` and `draw` may come in any order. Strings and chars use the escapes
+of Rust: \\ \" \' \n \r \t \0 and \u{7f}, so an event always stays on its line.
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+struct Point3D {
+	x: i64,
+	y: i64,
+	z: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum WebEvent {
+	PageLoad,
+	PageUnload,
+	KeyPress(char),
+	Paste(String),
+	Click { x: i64, y: i64 },
+	Draw(Point3D),
+}
+
+fn write_escaped(f: &mut fmt::Formatter, c: char, quote: char) -> fmt::Result {
+	match c {
+		'\\' => f.write_str("\\\\"),
+		'\n' => f.write_str("\\n"),
+		'\r' => f.write_str("\\r"),
+		'\t' => f.write_str("\\t"),
+		'\0' => f.write_str("\\0"),
+		c if c == quote => write!(f, "\\{}", c),
+		c if c.is_control() => write!(f, "\\u{{{:x}}}", c as u32),
+		c => f.write_char(c),
+	}
+}
+
+// The text of one event, without the '\n'
+impl fmt::Display for WebEvent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			WebEvent::PageLoad => f.write_str("pageload"),
+			WebEvent::PageUnload => f.write_str("pageunload"),
+			WebEvent::KeyPress(c) => {
+				f.write_str("keypress '")?;
+				write_escaped(f, *c, '\'')?;
+				f.write_str("'")
+			}
+			WebEvent::Paste(s) => {
+				f.write_str("paste \"")?;
+				for c in s.chars() {
+					write_escaped(f, c, '"')?;
+				}
+				f.write_str("\"")
+			}
+			WebEvent::Click { x, y } => write!(f, "click x={} y={}", x, y),
+			WebEvent::Draw(p) => write!(f, "draw x={} y={} z={}", p.x, p.y, p.z),
+		}
+	}
+}
+
+fn write_events(events: &[WebEvent]) -> String {
+	let mut text = String::new();
+	for event in events {
+		// writing into a String can't fail
+		writeln!(text, "{}", event).unwrap();
+	}
+	text
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ParseErrorKind {
+	UnknownEvent(String),
+	Expected(&'static str),
+	InvalidNumber,
+	InvalidEscape,
+	UnterminatedString,
+	UnknownField(String),
+	DuplicateField(&'static str),
+	MissingField(&'static str),
+	TrailingInput,
+}
+
+// line and column start at 1, the column counts chars
+#[derive(Debug, Clone, PartialEq)]
+struct ParseError {
+	line: usize,
+	column: usize,
+	kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseErrorKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ParseErrorKind::UnknownEvent(name) => write!(f, "unknown event `{}`", name),
+			ParseErrorKind::Expected(what) => write!(f, "expected {}", what),
+			ParseErrorKind::InvalidNumber => write!(f, "invalid number"),
+			ParseErrorKind::InvalidEscape => write!(f, "invalid escape sequence"),
+			ParseErrorKind::UnterminatedString => write!(f, "unterminated string"),
+			ParseErrorKind::UnknownField(name) => write!(f, "unknown field `{}`", name),
+			ParseErrorKind::DuplicateField(name) => write!(f, "field `{}` given twice", name),
+			ParseErrorKind::MissingField(name) => write!(f, "missing field `{}`", name),
+			ParseErrorKind::TrailingInput => write!(f, "unexpected input after the event"),
+		}
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+	}
+}
+
+// A position in one line of the input
+struct Cursor<'a> {
+	text: &'a str,
+	pos: usize,
+	line: usize,
+}
+
+impl<'a> Cursor<'a> {
+	fn peek(&self) -> Option<char> {
+		self.text[self.pos..].chars().next()
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.pos += c.len_utf8();
+		Some(c)
+	}
+
+	fn eat(&mut self, c: char) -> bool {
+		if self.peek() == Some(c) {
+			self.pos += c.len_utf8();
+			true
+		} else {
+			false
+		}
+	}
+
+	fn skip_spaces(&mut self) {
+		while matches!(self.peek(), Some(' ') | Some('\t')) {
+			self.pos += 1;
+		}
+	}
+
+	fn is_done(&self) -> bool {
+		self.pos == self.text.len()
+	}
+
+	fn error_at(&self, pos: usize, kind: ParseErrorKind) -> ParseError {
+		ParseError { line: self.line, column: self.text[..pos].chars().count() + 1, kind }
+	}
+
+	fn error(&self, kind: ParseErrorKind) -> ParseError {
+		self.error_at(self.pos, kind)
+	}
+
+	fn expect(&mut self, c: char, what: &'static str) -> Result<(), ParseError> {
+		if self.eat(c) { Ok(()) } else { Err(self.error(ParseErrorKind::Expected(what))) }
+	}
+
+	// [a-z0-9_]*
+	fn word(&mut self) -> &'a str {
+		let start = self.pos;
+		while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+			self.pos += 1;
+		}
+		&self.text[start..self.pos]
+	}
+
+	fn integer(&mut self) -> Result<i64, ParseError> {
+		let start = self.pos;
+		self.eat('-');
+		while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+			self.pos += 1;
+		}
+		// an empty or a too large number
+		self.text[start..self.pos].parse().map_err(|_| self.error_at(start, ParseErrorKind::InvalidNumber))
+	}
+
+	// the char after a '\'
+	fn escape(&mut self, start: usize) -> Result<char, ParseError> {
+		let invalid = |cursor: &Self| cursor.error_at(start, ParseErrorKind::InvalidEscape);
+		let c = match self.bump() {
+			Some('\\') => '\\',
+			Some('"') => '"',
+			Some('\'') => '\'',
+			Some('n') => '\n',
+			Some('r') => '\r',
+			Some('t') => '\t',
+			Some('0') => '\0',
+			Some('u') => {
+				if !self.eat('{') {
+					return Err(invalid(self));
+				}
+				let digits = self.word();
+				if !self.eat('}') || digits.is_empty() || digits.len() > 6 {
+					return Err(invalid(self));
+				}
+				let code = u32::from_str_radix(digits, 16).map_err(|_| invalid(self))?;
+				std::char::from_u32(code).ok_or_else(|| invalid(self))?
+			}
+			_ => return Err(invalid(self)),
+		};
+		Ok(c)
+	}
+
+	// one char of a quoted text, None at the closing quote
+	fn quoted_char(&mut self, quote: char, open: usize) -> Result<Option<char>, ParseError> {
+		let start = self.pos;
+		match self.bump() {
+			None => Err(self.error_at(open, ParseErrorKind::UnterminatedString)),
+			Some('\\') => self.escape(start).map(Some),
+			Some(c) if c == quote => Ok(None),
+			Some(c) => Ok(Some(c)),
+		}
+	}
+
+	fn string(&mut self) -> Result<String, ParseError> {
+		let open = self.pos;
+		self.expect('"', "a string")?;
+		let mut s = String::new();
+		while let Some(c) = self.quoted_char('"', open)? {
+			s.push(c);
+		}
+		Ok(s)
+	}
+
+	fn char_literal(&mut self) -> Result<char, ParseError> {
+		let open = self.pos;
+		self.expect('\'', "a char")?;
+		let at = self.pos;
+		match self.quoted_char('\'', open)? {
+			Some(c) => {
+				self.expect('\'', "a closing '")?;
+				Ok(c)
+			}
+			None => Err(self.error_at(at, ParseErrorKind::Expected("a char"))),
+		}
+	}
+
+	// `name=value` pairs in any order, the values come back in the order of `names`
+	fn fields<const N: usize>(&mut self, names: [&'static str; N]) -> Result<[i64; N], ParseError> {
+		let mut values = [None; N];
+		loop {
+			self.skip_spaces();
+			if self.is_done() {
+				break;
+			}
+			let start = self.pos;
+			let name = self.word();
+			let index = match names.iter().position(|&n| n == name) {
+				Some(index) => index,
+				None if name.is_empty() => return Err(self.error(ParseErrorKind::Expected("a field"))),
+				None => return Err(self.error_at(start, ParseErrorKind::UnknownField(name.to_string()))),
+			};
+			if values[index].is_some() {
+				return Err(self.error_at(start, ParseErrorKind::DuplicateField(names[index])));
+			}
+			self.expect('=', "`=`")?;
+			values[index] = Some(self.integer()?);
+			if !self.is_done() && !matches!(self.peek(), Some(' ') | Some('\t')) {
+				return Err(self.error(ParseErrorKind::Expected("a space")));
+			}
+		}
+
+		let mut result = [0; N];
+		for (i, value) in values.iter().enumerate() {
+			result[i] = value.ok_or_else(|| self.error(ParseErrorKind::MissingField(names[i])))?;
+		}
+		Ok(result)
+	}
+
+	fn event(&mut self) -> Result<WebEvent, ParseError> {
+		let start = self.pos;
+		let name = self.word();
+		let event = match name {
+			"pageload" => WebEvent::PageLoad,
+			"pageunload" => WebEvent::PageUnload,
+			"keypress" => {
+				self.skip_spaces();
+				WebEvent::KeyPress(self.char_literal()?)
+			}
+			"paste" => {
+				self.skip_spaces();
+				WebEvent::Paste(self.string()?)
+			}
+			"click" => {
+				let [x, y] = self.fields(["x", "y"])?;
+				WebEvent::Click { x, y }
+			}
+			"draw" => {
+				let [x, y, z] = self.fields(["x", "y", "z"])?;
+				WebEvent::Draw(Point3D { x, y, z })
+			}
+			"" => return Err(self.error(ParseErrorKind::Expected("an event"))),
+			name => return Err(self.error_at(start, ParseErrorKind::UnknownEvent(name.to_string()))),
+		};
+
+		self.skip_spaces();
+		if self.is_done() { Ok(event) } else { Err(self.error(ParseErrorKind::TrailingInput)) }
+	}
+}
+
+// None for a blank or a comment line
+fn parse_line(text: &str, line: usize) -> Result<Option<WebEvent>, ParseError> {
+	let mut cursor = Cursor { text, pos: 0, line };
+	cursor.skip_spaces();
+	if cursor.is_done() || cursor.peek() == Some('#') {
+		return Ok(None);
+	}
+	cursor.event().map(Some)
+}
+
+fn parse_events(text: &str) -> Result<Vec<WebEvent>, ParseError> {
+	let mut events = Vec::new();
+	for (i, line) in text.lines().enumerate() {
+		if let Some(event) = parse_line(line, i + 1)? {
+			events.push(event);
+		}
+	}
+	Ok(events)
+}
+
+// a single event, on line 1
+impl FromStr for WebEvent {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut cursor = Cursor { text: s, pos: 0, line: 1 };
+		cursor.skip_spaces();
+		cursor.event()
+	}
+}
+
+// the chars the escaping has to deal with
+const TRICKY_CHARS: [char; 20] = [
+	'a', 'Z', '0', ' ', '"', '\'', '\\', '\n', '\r', '\t', '\0', '\u{7f}', '\u{1b}', 'é', '中', '🦀', '#', '=', '{', '}',
+];
+
+// every variant, with the tricky chars and the extreme numbers
+fn tricky_events() -> Vec<WebEvent> {
+	let mut events = vec![WebEvent::PageLoad, WebEvent::PageUnload, WebEvent::Paste(String::new())];
+	events.extend(TRICKY_CHARS.iter().map(|&c| WebEvent::KeyPress(c)));
+	events.extend(TRICKY_CHARS.iter().map(|&c| WebEvent::Paste(format!("{}{}{}", c, c, c))));
+	events.push(WebEvent::Paste(TRICKY_CHARS.iter().collect()));
+	for &n in [i64::MIN, -1, 0, 1, i64::MAX].iter() {
+		events.push(WebEvent::Click { x: n, y: -n.saturating_add(1) });
+		events.push(WebEvent::Draw(Point3D { x: n, y: 0, z: n / 2 }));
+	}
+	events
+}
+
+// generated events for the round trip, the same ones on every run
+struct Lcg(u64);
+
+impl Lcg {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+		self.0 >> 33
+	}
+
+	fn below(&mut self, n: u64) -> u64 {
+		self.next() % n
+	}
+
+	fn char(&mut self) -> char {
+		TRICKY_CHARS[self.below(TRICKY_CHARS.len() as u64) as usize]
+	}
+
+	// small values and the extreme ones
+	fn integer(&mut self) -> i64 {
+		match self.below(4) {
+			0 => i64::MIN,
+			1 => i64::MAX,
+			_ => self.below(2001) as i64 - 1000,
+		}
+	}
+
+	fn event(&mut self) -> WebEvent {
+		match self.below(6) {
+			0 => WebEvent::PageLoad,
+			1 => WebEvent::PageUnload,
+			2 => WebEvent::KeyPress(self.char()),
+			3 => {
+				let len = self.below(12);
+				WebEvent::Paste((0..len).map(|_| self.char()).collect())
+			}
+			4 => WebEvent::Click { x: self.integer(), y: self.integer() },
+			_ => WebEvent::Draw(Point3D { x: self.integer(), y: self.integer(), z: self.integer() }),
+		}
+	}
+}
+
+// a damaged text gives an error, never a panic, and the error is on a line of the text
+fn assert_error_in_text(text: &str) {
+	if let Err(e) = parse_events(text) {
+		assert!(e.line >= 1 && e.line <= text.lines().count(), "{:?}: {}", text, e);
+		assert!(e.column >= 1 && e.column <= text.lines().nth(e.line - 1).unwrap().chars().count() + 1, "{:?}: {}", text, e);
+	}
+}
+
+fn sample_events() -> Vec<WebEvent> {
+	vec![
+		WebEvent::PageLoad,
+		WebEvent::KeyPress('x'),
+		WebEvent::Paste(String::from("my text")),
+		WebEvent::Click { x: 20, y: 80 },
+		WebEvent::Draw(Point3D { x: 10, y: 20, z: -5 }),
+		WebEvent::PageUnload,
+	]
+}
+
+fn text_write() {
+	print_knowledage_point("text write");
+
+	let text = write_events(&sample_events());
+	print!("{}", text);
+	assert_eq!(text, "\
+pageload
+keypress 'x'
+paste \"my text\"
+click x=20 y=80
+draw x=10 y=20 z=-5
+pageunload
+");
+
+	let tricky = WebEvent::Paste(String::from("say \"hi\"\\\n\tbye\u{7}"));
+	println!("{}", tricky);
+	assert_eq!(tricky.to_string(), r#"paste "say \"hi\"\\\n\tbye\u{7}""#);
+	assert_eq!(WebEvent::KeyPress('\'').to_string(), r"keypress '\''");
+	assert_eq!(WebEvent::KeyPress('"').to_string(), r#"keypress '"'"#);
+}
+
+fn text_parse() {
+	print_knowledage_point("text parse");
+
+	let text = "
+# a session
+pageload
+  keypress   'x'
+paste \"my text\"
+
+click y=80 x=20
+draw z=-5 x=10 y=20
+pageunload
+";
+	assert_eq!(parse_events(text), Ok(sample_events()));
+	assert_eq!(r#"paste "\u{1F980} \'ok\'""#.parse(), Ok(WebEvent::Paste(String::from("🦀 'ok'"))));
+	assert_eq!("keypress '\\n'".parse(), Ok(WebEvent::KeyPress('\n')));
+	// Windows line ends
+	assert_eq!(parse_events("pageload\r\nclick x=1 y=2\r\n"), Ok(vec![WebEvent::PageLoad, WebEvent::Click { x: 1, y: 2 }]));
+
+	let error = |line: usize, column: usize, kind: ParseErrorKind| Err(ParseError { line, column, kind });
+	let cases = [
+		("pageload\nscroll x=1", error(2, 1, ParseErrorKind::UnknownEvent(String::from("scroll")))),
+		("click x=20", error(1, 11, ParseErrorKind::MissingField("y"))),
+		("click x=20 x=3 y=1", error(1, 12, ParseErrorKind::DuplicateField("x"))),
+		("click x=20 w=3", error(1, 12, ParseErrorKind::UnknownField(String::from("w")))),
+		("click x 20", error(1, 8, ParseErrorKind::Expected("`=`"))),
+		("click x=2a y=1", error(1, 10, ParseErrorKind::Expected("a space"))),
+		("draw x=1 y=99999999999999999999 z=0", error(1, 12, ParseErrorKind::InvalidNumber)),
+		("draw x=1 y=- z=0", error(1, 12, ParseErrorKind::InvalidNumber)),
+		("paste \"abc", error(1, 7, ParseErrorKind::UnterminatedString)),
+		("paste \"a\\qb\"", error(1, 9, ParseErrorKind::InvalidEscape)),
+		("paste \"\\u{d800}\"", error(1, 8, ParseErrorKind::InvalidEscape)),
+		("paste abc", error(1, 7, ParseErrorKind::Expected("a string"))),
+		("paste \"a\" b", error(1, 11, ParseErrorKind::TrailingInput)),
+		("keypress 'ab'", error(1, 12, ParseErrorKind::Expected("a closing '"))),
+		("keypress ''", error(1, 11, ParseErrorKind::Expected("a char"))),
+		("pageload now", error(1, 10, ParseErrorKind::TrailingInput)),
+		// columns count chars, not bytes
+		("paste \"é\" 中", error(1, 11, ParseErrorKind::TrailingInput)),
+		("=1", error(1, 1, ParseErrorKind::Expected("an event"))),
+	];
+
+	for (text, expected) in cases.iter() {
+		let result = parse_events(text);
+		if let Err(ref e) = result {
+			println!("{:<40} {}", format!("{:?}", text), e);
+		}
+		assert_eq!(&result, expected);
+	}
+}
+
+// property style: whatever the event, the text parses back to it
+fn text_round_trip() {
+	print_knowledage_point("text round trip");
+
+	let events = tricky_events();
+	for event in events.iter() {
+		let text = event.to_string();
+		assert!(!text.contains('\n'), "{:?} spans lines", text);
+		assert_eq!(text.parse::<WebEvent>().as_ref(), Ok(event));
+	}
+	assert_eq!(parse_events(&write_events(&events)), Ok(events));
+
+	// every position of a sample damaged
+	let mut sample = sample_events();
+	sample.push(WebEvent::Paste(String::from("a \"b\" \\ \n 🦀")));
+	let chars: Vec<char> = write_events(&sample).chars().collect();
+	for at in 0..chars.len() {
+		let mut removed = chars.clone();
+		removed.remove(at);
+		let mut inserted = chars.clone();
+		inserted.insert(at, '"');
+		for damaged in [removed, inserted, chars[..at].to_vec()].iter() {
+			assert_error_in_text(&damaged.iter().collect::<String>());
+		}
+	}
+
+	// generated events, from a fixed seed
+	let mut rng = Lcg(17);
+	for _ in 0..2000 {
+		let event = rng.event();
+		let text = event.to_string();
+		assert!(!text.contains('\n'), "{:?} spans lines", text);
+		assert_eq!(text.parse::<WebEvent>(), Ok(event));
+	}
+	for _ in 0..200 {
+		let len = rng.below(20) as usize;
+		let events: Vec<WebEvent> = (0..len).map(|_| rng.event()).collect();
+		assert_eq!(parse_events(&write_events(&events)), Ok(events));
+	}
+	for _ in 0..2000 {
+		let events: Vec<WebEvent> = (0..3).map(|_| rng.event()).collect();
+		let mut chars: Vec<char> = write_events(&events).chars().collect();
+		let at = rng.below(chars.len() as u64) as usize;
+		match rng.below(3) {
+			0 => { chars.remove(at); }
+			1 => chars.insert(at, rng.char()),
+			_ => chars.truncate(at),
+		}
+		assert_error_in_text(&chars.into_iter().collect::<String>());
+	}
+	println!("round trips ok");
+}
+
+fn main() {
+	text_write();
+	text_parse();
+	text_round_trip();
+}
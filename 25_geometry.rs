@@ -0,0 +1,660 @@
+fn print_knowledage_point(s: &str) {
+	println!("~~~~~~~{}~~~~~~~", s);
+}
+
+use std::f64::consts::PI;
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/*
+07_generics.rs bounds `area` with `HasArea`, only implemented for its `Rectangle { width, height }`,
+12_std_lib_types.rs has a `Rectangle { top_left, bottom_right }` of `Point`s and 04_type_conversion.rs a `Circle`.
+Here they are one set of shapes behind the `Shape` trait: area, perimeter, bounding box,
+contains-point, centroid, and whether two shapes intersect.
+
+The y axis goes up, as in 12_std_lib_types.rs: `top_left` has the larger y.
+A shape is closed, a point on its border is inside it and touching shapes intersect.
+*/
+
+// tolerance of the on-the-border tests
+const EPS: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point {
+	x: f64,
+	y: f64,
+}
+
+impl Point {
+	fn new(x: f64, y: f64) -> Self {
+		Point { x, y }
+	}
+
+	fn distance(self, other: Point) -> f64 {
+		(self - other).norm()
+	}
+
+	fn norm(self) -> f64 {
+		self.x.hypot(self.y)
+	}
+
+	fn dot(self, other: Point) -> f64 {
+		self.x * other.x + self.y * other.y
+	}
+
+	// > 0 when `other` is counter-clockwise from `self`
+	fn cross(self, other: Point) -> f64 {
+		self.x * other.y - self.y * other.x
+	}
+
+	fn scale(self, k: f64) -> Point {
+		Point::new(self.x * k, self.y * k)
+	}
+}
+
+impl Add for Point {
+	type Output = Point;
+
+	fn add(self, rhs: Point) -> Point {
+		Point::new(self.x + rhs.x, self.y + rhs.y)
+	}
+}
+
+impl Sub for Point {
+	type Output = Point;
+
+	fn sub(self, rhs: Point) -> Point {
+		Point::new(self.x - rhs.x, self.y - rhs.y)
+	}
+}
+
+impl fmt::Display for Point {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "({}, {})", self.x, self.y)
+	}
+}
+
+// What the intersection tests work on: a circle, or the vertices of a polygon
+#[derive(Debug)]
+enum Outline {
+	Circle { center: Point, radius: f64 },
+	Polygon(Vec<Point>),
+}
+
+trait Shape {
+	fn area(&self) -> f64;
+	fn perimeter(&self) -> f64;
+	fn bounding_box(&self) -> Rectangle;
+	fn contains(&self, p: Point) -> bool;
+	fn centroid(&self) -> Point;
+	fn outline(&self) -> Outline;
+
+	fn intersects(&self, other: &dyn Shape) -> bool {
+		if !self.bounding_box().overlaps(&other.bounding_box()) {
+			return false;
+		}
+		match (self.outline(), other.outline()) {
+			(Outline::Circle { center: c1, radius: r1 }, Outline::Circle { center: c2, radius: r2 }) => {
+				c1.distance(c2) <= r1 + r2 + EPS
+			}
+			(Outline::Circle { center, radius }, Outline::Polygon(vertices))
+			| (Outline::Polygon(vertices), Outline::Circle { center, radius }) => {
+				// the center inside, or an edge close enough to it
+				polygon_contains(&vertices, center)
+					|| edges(&vertices).any(|(a, b)| segment_distance(center, a, b) <= radius + EPS)
+			}
+			(Outline::Polygon(a), Outline::Polygon(b)) => {
+				// crossing borders, or one polygon inside the other
+				edges(&a).any(|(p1, p2)| edges(&b).any(|(q1, q2)| segments_intersect(p1, p2, q1, q2)))
+					|| polygon_contains(&a, b[0])
+					|| polygon_contains(&b, a[0])
+			}
+		}
+	}
+}
+
+// The closing edge included
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+	vertices.iter().zip(vertices.iter().cycle().skip(1)).map(|(a, b)| (*a, *b))
+}
+
+fn on_segment(p: Point, a: Point, b: Point) -> bool {
+	(b - a).cross(p - a).abs() <= EPS
+		&& p.x >= a.x.min(b.x) - EPS && p.x <= a.x.max(b.x) + EPS
+		&& p.y >= a.y.min(b.y) - EPS && p.y <= a.y.max(b.y) + EPS
+}
+
+fn segments_intersect(p1: Point, p2: Point, q1: Point, q2: Point) -> bool {
+	let d1 = (q2 - q1).cross(p1 - q1);
+	let d2 = (q2 - q1).cross(p2 - q1);
+	let d3 = (p2 - p1).cross(q1 - p1);
+	let d4 = (p2 - p1).cross(q2 - p1);
+	// each segment has its ends on both sides of the other
+	if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+		return true;
+	}
+	// or an end touches the other segment
+	on_segment(p1, q1, q2) || on_segment(p2, q1, q2) || on_segment(q1, p1, p2) || on_segment(q2, p1, p2)
+}
+
+fn segment_distance(p: Point, a: Point, b: Point) -> f64 {
+	let ab = b - a;
+	let len2 = ab.dot(ab);
+	if len2 == 0.0 {
+		return p.distance(a);
+	}
+	let t = ((p - a).dot(ab) / len2).clamp(0.0, 1.0);
+	p.distance(a + ab.scale(t))
+}
+
+// ray casting to the right of `p`, the border counts as inside
+fn polygon_contains(vertices: &[Point], p: Point) -> bool {
+	if edges(vertices).any(|(a, b)| on_segment(p, a, b)) {
+		return true;
+	}
+	let mut inside = false;
+	for (a, b) in edges(vertices) {
+		if (a.y > p.y) != (b.y > p.y) {
+			let x = a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y);
+			if p.x < x {
+				inside = !inside;
+			}
+		}
+	}
+	inside
+}
+
+// shoelace formula, > 0 for counter-clockwise vertices
+fn signed_area(vertices: &[Point]) -> f64 {
+	edges(vertices).map(|(a, b)| a.cross(b)).sum::<f64>() / 2.0
+}
+
+fn polygon_perimeter(vertices: &[Point]) -> f64 {
+	edges(vertices).map(|(a, b)| a.distance(b)).sum()
+}
+
+fn polygon_bounding_box(vertices: &[Point]) -> Rectangle {
+	let first = Rectangle::new(vertices[0], vertices[0]);
+	vertices.iter().fold(first, |bbox, p| Rectangle {
+		top_left: Point::new(bbox.left().min(p.x), bbox.top().max(p.y)),
+		bottom_right: Point::new(bbox.right().max(p.x), bbox.bottom().min(p.y)),
+	})
+}
+
+// The `Rectangle` of 12_std_lib_types.rs, also the bounding box of every shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rectangle {
+	top_left: Point,
+	bottom_right: Point,
+}
+
+impl Rectangle {
+	// from any two opposite corners
+	fn new(a: Point, b: Point) -> Self {
+		Rectangle {
+			top_left: Point::new(a.x.min(b.x), a.y.max(b.y)),
+			bottom_right: Point::new(a.x.max(b.x), a.y.min(b.y)),
+		}
+	}
+
+	// the `Rectangle { width, height }` of 07_generics.rs, at the origin
+	fn with_size(width: f64, height: f64) -> Self {
+		Rectangle::new(Point::new(0.0, height), Point::new(width, 0.0))
+	}
+
+	fn left(&self) -> f64 { self.top_left.x }
+	fn right(&self) -> f64 { self.bottom_right.x }
+	fn top(&self) -> f64 { self.top_left.y }
+	fn bottom(&self) -> f64 { self.bottom_right.y }
+	fn width(&self) -> f64 { self.right() - self.left() }
+	fn height(&self) -> f64 { self.top() - self.bottom() }
+
+	fn overlaps(&self, other: &Rectangle) -> bool {
+		self.left() <= other.right() && other.left() <= self.right()
+			&& self.bottom() <= other.top() && other.bottom() <= self.top()
+	}
+
+	// counter-clockwise from the bottom left
+	fn corners(&self) -> [Point; 4] {
+		[
+			Point::new(self.left(), self.bottom()),
+			self.bottom_right,
+			Point::new(self.right(), self.top()),
+			self.top_left,
+		]
+	}
+}
+
+impl Shape for Rectangle {
+	fn area(&self) -> f64 {
+		self.width() * self.height()
+	}
+
+	fn perimeter(&self) -> f64 {
+		2.0 * (self.width() + self.height())
+	}
+
+	fn bounding_box(&self) -> Rectangle {
+		*self
+	}
+
+	fn contains(&self, p: Point) -> bool {
+		p.x >= self.left() && p.x <= self.right() && p.y >= self.bottom() && p.y <= self.top()
+	}
+
+	fn centroid(&self) -> Point {
+		(self.top_left + self.bottom_right).scale(0.5)
+	}
+
+	fn outline(&self) -> Outline {
+		Outline::Polygon(self.corners().to_vec())
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Triangle {
+	a: Point,
+	b: Point,
+	c: Point,
+}
+
+impl Triangle {
+	fn new(a: Point, b: Point, c: Point) -> Self {
+		Triangle { a, b, c }
+	}
+
+	// the `Triangle { length, height }` of 07_generics.rs: a right triangle at the origin
+	fn with_base(length: f64, height: f64) -> Self {
+		Triangle::new(Point::new(0.0, 0.0), Point::new(length, 0.0), Point::new(0.0, height))
+	}
+
+	fn vertices(&self) -> [Point; 3] {
+		[self.a, self.b, self.c]
+	}
+}
+
+impl Shape for Triangle {
+	fn area(&self) -> f64 {
+		signed_area(&self.vertices()).abs()
+	}
+
+	fn perimeter(&self) -> f64 {
+		polygon_perimeter(&self.vertices())
+	}
+
+	fn bounding_box(&self) -> Rectangle {
+		polygon_bounding_box(&self.vertices())
+	}
+
+	fn contains(&self, p: Point) -> bool {
+		polygon_contains(&self.vertices(), p)
+	}
+
+	fn centroid(&self) -> Point {
+		(self.a + self.b + self.c).scale(1.0 / 3.0)
+	}
+
+	fn outline(&self) -> Outline {
+		Outline::Polygon(self.vertices().to_vec())
+	}
+}
+
+// The `Circle` of 04_type_conversion.rs, now with a center
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Circle {
+	center: Point,
+	radius: f64,
+}
+
+// a radius is a length, -2 gives the same circle as 2
+impl From<i32> for Circle {
+	fn from(v: i32) -> Self {
+		Circle { center: Point::new(0.0, 0.0), radius: (v as f64).abs() }
+	}
+}
+
+impl fmt::Display for Circle {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "Circle of radius {}", self.radius)
+	}
+}
+
+impl Shape for Circle {
+	fn area(&self) -> f64 {
+		PI * self.radius * self.radius
+	}
+
+	fn perimeter(&self) -> f64 {
+		2.0 * PI * self.radius
+	}
+
+	fn bounding_box(&self) -> Rectangle {
+		let r = Point::new(self.radius, self.radius);
+		Rectangle::new(self.center - r, self.center + r)
+	}
+
+	fn contains(&self, p: Point) -> bool {
+		self.center.distance(p) <= self.radius + EPS
+	}
+
+	fn centroid(&self) -> Point {
+		self.center
+	}
+
+	fn outline(&self) -> Outline {
+		Outline::Circle { center: self.center, radius: self.radius }
+	}
+}
+
+// A simple polygon: its edges don't cross, in either direction
+#[derive(Debug, Clone, PartialEq)]
+struct Polygon {
+	vertices: Vec<Point>,
+}
+
+impl Polygon {
+	// None under 3 vertices
+	fn new(vertices: Vec<Point>) -> Option<Self> {
+		if vertices.len() < 3 {
+			None
+		} else {
+			Some(Polygon { vertices })
+		}
+	}
+}
+
+impl Shape for Polygon {
+	fn area(&self) -> f64 {
+		signed_area(&self.vertices).abs()
+	}
+
+	fn perimeter(&self) -> f64 {
+		polygon_perimeter(&self.vertices)
+	}
+
+	fn bounding_box(&self) -> Rectangle {
+		polygon_bounding_box(&self.vertices)
+	}
+
+	fn contains(&self, p: Point) -> bool {
+		polygon_contains(&self.vertices, p)
+	}
+
+	fn centroid(&self) -> Point {
+		let area = signed_area(&self.vertices);
+		if area.abs() <= EPS {
+			// flat, the mean of the vertices
+			let sum = self.vertices.iter().fold(Point::new(0.0, 0.0), |sum, p| sum + *p);
+			return sum.scale(1.0 / self.vertices.len() as f64);
+		}
+		let sum = edges(&self.vertices).fold(Point::new(0.0, 0.0), |sum, (a, b)| sum + (a + b).scale(a.cross(b)));
+		sum.scale(1.0 / (6.0 * area))
+	}
+
+	fn outline(&self) -> Outline {
+		Outline::Polygon(self.vertices.clone())
+	}
+}
+
+// `area<T: HasArea>` of 07_generics.rs, for any shape
+fn describe<T: Shape>(name: &str, shape: &T) -> String {
+	let bbox = shape.bounding_box();
+	format!("{}: area {:.2}, perimeter {:.2}, centroid {}, box {} to {}",
+		name, shape.area(), shape.perimeter(), shape.centroid(), bbox.top_left, bbox.bottom_right)
+}
+
+fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+	shapes.iter().map(|s| s.area()).sum()
+}
+
+fn close(a: f64, b: f64) -> bool {
+	(a - b).abs() < 1e-9
+}
+
+fn shape_measures() {
+	print_knowledage_point("shape measures");
+
+	let rectangle = Rectangle::with_size(3.0, 4.0);
+	let triangle = Triangle::with_base(3.0, 4.0);
+	let circle = Circle::from(2);
+	// an L: a 2x2 square with a 1x1 notch
+	let l_shape = Polygon::new(vec![
+		Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 1.0),
+		Point::new(1.0, 1.0), Point::new(1.0, 2.0), Point::new(0.0, 2.0),
+	]).unwrap();
+
+	println!("{}", describe("rectangle", &rectangle));
+	println!("{}", describe("triangle", &triangle));
+	println!("{}", describe(&circle.to_string(), &circle));
+	println!("{}", describe("L", &l_shape));
+
+	// the same areas as HasArea
+	assert_eq!(rectangle.area(), 12.0);
+	assert_eq!(rectangle.perimeter(), 14.0);
+	assert_eq!(rectangle.centroid(), Point::new(1.5, 2.0));
+	assert_eq!(triangle.area(), 6.0);
+	assert_eq!(triangle.perimeter(), 12.0);
+	assert_eq!(triangle.centroid(), Point::new(1.0, 4.0 / 3.0));
+	assert_eq!(triangle.bounding_box(), rectangle);
+	assert!(close(circle.area(), 4.0 * PI));
+	assert!(close(circle.perimeter(), 4.0 * PI));
+	assert_eq!(Circle::from(-2), circle);
+	assert_eq!(circle.bounding_box(), Rectangle::new(Point::new(-2.0, 2.0), Point::new(2.0, -2.0)));
+
+	assert_eq!(l_shape.area(), 3.0);
+	assert_eq!(l_shape.perimeter(), 8.0);
+	// pulled away from the notch
+	let c = l_shape.centroid();
+	assert!(close(c.x, 5.0 / 6.0) && close(c.y, 5.0 / 6.0));
+	assert_eq!(l_shape.bounding_box(), Rectangle::with_size(2.0, 2.0));
+
+	// the orientation of the vertices doesn't matter
+	let mut reversed = l_shape.vertices.clone();
+	reversed.reverse();
+	let reversed = Polygon::new(reversed).unwrap();
+	assert_eq!(reversed.area(), 3.0);
+	assert_eq!(reversed.centroid(), l_shape.centroid());
+
+	assert_eq!(Polygon::new(vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0)]), None);
+
+	let shapes: Vec<Box<dyn Shape>> = vec![Box::new(rectangle), Box::new(triangle), Box::new(l_shape)];
+	assert_eq!(total_area(&shapes), 21.0);
+}
+
+fn shape_contains() {
+	print_knowledage_point("shape contains");
+
+	// the rectangle of 12_std_lib_types.rs
+	let rectangle = Rectangle::new(Point::new(0.0, 0.0), Point::new(3.0, -4.0));
+	assert_eq!(rectangle.top_left, Point::new(0.0, 0.0));
+	assert!(rectangle.contains(Point::new(1.0, -1.0)));
+	// the border is inside
+	assert!(rectangle.contains(Point::new(3.0, -4.0)));
+	assert!(!rectangle.contains(Point::new(1.0, 1.0)));
+
+	let triangle = Triangle::with_base(3.0, 4.0);
+	assert!(triangle.contains(Point::new(1.0, 1.0)));
+	assert!(triangle.contains(Point::new(1.5, 2.0)));
+	assert!(!triangle.contains(Point::new(2.0, 2.0)));
+
+	let circle = Circle { center: Point::new(1.0, 1.0), radius: 1.0 };
+	assert!(circle.contains(Point::new(2.0, 1.0)));
+	assert!(!circle.contains(Point::new(1.8, 1.8)));
+	// a computed point of the border, a bit off after the rounding
+	let circle = Circle { center: Point::new(0.1, 0.2), radius: 0.3 };
+	for i in 0..12 {
+		let angle = PI * i as f64 / 6.0;
+		let border = circle.center + Point::new(angle.cos(), angle.sin()).scale(circle.radius);
+		assert!(circle.contains(border));
+		assert!(circle.intersects(&Triangle::new(border, border + Point::new(1.0, 0.0), border + Point::new(0.0, 1.0))));
+	}
+
+	let l_shape = Polygon::new(vec![
+		Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 1.0),
+		Point::new(1.0, 1.0), Point::new(1.0, 2.0), Point::new(0.0, 2.0),
+	]).unwrap();
+	for (p, inside) in [((0.5, 1.5), true), ((1.5, 0.5), true), ((1.5, 1.5), false), ((1.0, 1.5), true), ((2.0, 2.0), false)] {
+		println!("L contains ({}, {}): {}", p.0, p.1, inside);
+		assert_eq!(l_shape.contains(Point::new(p.0, p.1)), inside);
+	}
+	// a ray through a vertex is counted once
+	assert!(l_shape.contains(Point::new(0.5, 1.0)));
+	assert!(!l_shape.contains(Point::new(-0.5, 1.0)));
+}
+
+fn shape_intersection() {
+	print_knowledage_point("shape intersection");
+
+	let rectangle = Rectangle::with_size(2.0, 2.0);
+	let far = Rectangle::new(Point::new(5.0, 5.0), Point::new(6.0, 6.0));
+	let touching = Rectangle::new(Point::new(2.0, 0.0), Point::new(3.0, 1.0));
+	// a cross: no vertex inside the other, only the edges cross
+	let tall = Rectangle::new(Point::new(0.5, -1.0), Point::new(1.5, 3.0));
+	let inner = Rectangle::new(Point::new(0.5, 0.5), Point::new(1.0, 1.0));
+
+	assert!(!rectangle.intersects(&far));
+	assert!(rectangle.intersects(&touching));
+	assert!(rectangle.intersects(&tall));
+	assert!(rectangle.intersects(&inner) && inner.intersects(&rectangle));
+
+	// the boxes overlap, the triangle stays under the diagonal
+	let triangle = Triangle::new(Point::new(3.0, 0.0), Point::new(3.0, 3.0), Point::new(0.0, 3.0));
+	let corner = Triangle::with_base(1.0, 1.0);
+	assert!(corner.bounding_box().overlaps(&triangle.bounding_box()));
+	assert!(!corner.intersects(&triangle));
+	assert!(rectangle.intersects(&triangle));
+
+	let circle = Circle { center: Point::new(4.0, 1.0), radius: 1.0 };
+	assert!(!circle.intersects(&rectangle));
+	let circle = Circle { center: Point::new(3.0, 1.0), radius: 1.0 };
+	assert!(circle.intersects(&rectangle) && rectangle.intersects(&circle));
+	// near the corner: inside the box, outside the circle
+	let circle = Circle { center: Point::new(2.8, 2.8), radius: 1.0 };
+	assert!(circle.bounding_box().overlaps(&rectangle));
+	assert!(!circle.intersects(&rectangle));
+	// a big circle around the rectangle
+	assert!(Circle::from(10).intersects(&rectangle));
+
+	assert!(Circle::from(1).intersects(&Circle { center: Point::new(2.0, 0.0), radius: 1.0 }));
+	assert!(!Circle::from(1).intersects(&Circle { center: Point::new(2.0, 0.5), radius: 1.0 }));
+
+	// the notch of the L is empty
+	let l_shape = Polygon::new(vec![
+		Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 1.0),
+		Point::new(1.0, 1.0), Point::new(1.0, 2.0), Point::new(0.0, 2.0),
+	]).unwrap();
+	let in_notch = Circle { center: Point::new(1.6, 1.6), radius: 0.3 };
+	assert!(!l_shape.intersects(&in_notch));
+	assert!(rectangle.intersects(&in_notch));
+
+	let shapes: Vec<(&str, Box<dyn Shape>)> = vec![
+		("rectangle", Box::new(rectangle)),
+		("triangle", Box::new(triangle)),
+		("L", Box::new(l_shape)),
+		("notch circle", Box::new(in_notch)),
+	];
+	for (i, (a_name, a)) in shapes.iter().enumerate() {
+		for (b_name, b) in &shapes[i + 1..] {
+			println!("{} x {}: {}", a_name, b_name, a.intersects(b.as_ref()));
+		}
+	}
+}
+
+// every point of a grid over the shapes below, their borders fall on it
+fn grid() -> Vec<Point> {
+	let steps = (-12..=12).map(|i| i as f64 / 2.0);
+	steps.clone().flat_map(|x| steps.clone().map(move |y| Point::new(x, y))).collect()
+}
+
+fn rectangles() -> Vec<Rectangle> {
+	vec![
+		Rectangle::with_size(3.0, 4.0),
+		Rectangle::new(Point::new(-5.0, 1.0), Point::new(-1.0, -2.0)),
+		// flat ones
+		Rectangle::new(Point::new(-2.0, 3.0), Point::new(2.0, 3.0)),
+		Rectangle::new(Point::new(1.0, 1.0), Point::new(1.0, 1.0)),
+	]
+}
+
+fn triangles() -> Vec<Triangle> {
+	vec![
+		Triangle::with_base(3.0, 4.0),
+		// clockwise
+		Triangle::new(Point::new(-4.0, -4.0), Point::new(-1.0, 2.0), Point::new(2.0, -4.0)),
+		Triangle::new(Point::new(3.0, 0.0), Point::new(3.0, 3.0), Point::new(0.0, 3.0)),
+		// flat
+		Triangle::new(Point::new(-3.0, -3.0), Point::new(0.0, 0.0), Point::new(3.0, 3.0)),
+	]
+}
+
+fn shapes() -> Vec<Box<dyn Shape>> {
+	let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
+	for rectangle in rectangles() {
+		shapes.push(Box::new(rectangle));
+	}
+	for triangle in triangles() {
+		shapes.push(Box::new(triangle));
+	}
+	shapes.push(Box::new(Circle::from(2)));
+	shapes.push(Box::new(Circle { center: Point::new(-4.0, 3.0), radius: 1.5 }));
+	shapes.push(Box::new(Circle { center: Point::new(4.5, -4.5), radius: 0.5 }));
+	// the L, and a concave arrow
+	shapes.push(Box::new(Polygon::new(vec![
+		Point::new(0.0, 0.0), Point::new(2.0, 0.0), Point::new(2.0, 1.0),
+		Point::new(1.0, 1.0), Point::new(1.0, 2.0), Point::new(0.0, 2.0),
+	]).unwrap()));
+	shapes.push(Box::new(Polygon::new(vec![
+		Point::new(-5.0, -5.0), Point::new(5.0, -1.0), Point::new(-5.0, 3.0), Point::new(-2.0, -1.0),
+	]).unwrap()));
+	shapes
+}
+
+fn shape_properties() {
+	print_knowledage_point("shape properties");
+
+	let grid = grid();
+	// a rectangle, a triangle and their polygons agree
+	for rectangle in rectangles() {
+		let as_polygon = Polygon { vertices: rectangle.corners().to_vec() };
+		assert!(close(rectangle.area(), as_polygon.area()));
+		for &p in grid.iter() {
+			assert_eq!(rectangle.contains(p), as_polygon.contains(p), "{:?} {:?}", rectangle, p);
+		}
+	}
+	for triangle in triangles() {
+		let as_polygon = Polygon { vertices: triangle.vertices().to_vec() };
+		assert!(close(triangle.area(), as_polygon.area()));
+		assert!(close(triangle.perimeter(), as_polygon.perimeter()));
+		assert_eq!(triangle.bounding_box(), as_polygon.bounding_box());
+		for &p in grid.iter() {
+			assert_eq!(triangle.contains(p), as_polygon.contains(p), "{:?} {:?}", triangle, p);
+		}
+	}
+
+	let shapes = shapes();
+	let mut intersecting = 0;
+	for a in shapes.iter() {
+		// the centroid is an average of the points of the shape
+		assert!(a.bounding_box().contains(a.centroid()));
+		// b == a too, a shape meets itself
+		for b in shapes.iter() {
+			let intersects = a.intersects(b.as_ref());
+			assert_eq!(intersects, b.intersects(a.as_ref()));
+			// a point in both shapes is a witness of the intersection
+			if grid.iter().any(|&p| a.contains(p) && b.contains(p)) {
+				assert!(intersects);
+			}
+			if intersects {
+				intersecting += 1;
+			}
+		}
+	}
+	println!("{} intersecting pairs out of {}", intersecting, shapes.len() * shapes.len());
+}
+
+fn main() {
+	shape_measures();
+	shape_contains();
+	shape_intersection();
+	shape_properties();
+}